@@ -0,0 +1,29 @@
+use std::env;
+use std::str::FromStr;
+
+pub fn flag(name: &str) -> bool {
+    env::args().skip(1).any(|arg| arg == name)
+}
+
+pub fn value(name: &str) -> Option<String> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    args.iter().position(|arg| arg == name).map(|i| {
+        args.get(i + 1).cloned().unwrap_or_else(|| panic!("Missing value for {}", name))
+    })
+}
+
+pub fn values(name: &str) -> Vec<String> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    args.iter()
+        .enumerate()
+        .filter(|(_, arg)| *arg == name)
+        .map(|(i, _)| args.get(i + 1).cloned().unwrap_or_else(|| panic!("Missing value for {}", name)))
+        .collect()
+}
+
+pub fn value_or<T: FromStr>(name: &str, default: T) -> T {
+    match value(name) {
+        Some(s) => s.parse::<T>().unwrap_or_else(|_| panic!("Invalid value for {}: {}", name, s)),
+        None => default,
+    }
+}
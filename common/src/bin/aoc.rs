@@ -0,0 +1,208 @@
+use std::env;
+use std::path::PathBuf;
+use std::process::exit;
+use aoc2024_common::dashboard;
+use aoc2024_common::describe;
+use aoc2024_common::file;
+use aoc2024_common::gen::{self, GenParams};
+use aoc2024_common::leaderboard;
+use aoc2024_common::net::{self, SubmitOutcome};
+use aoc2024_common::run::{self, Status};
+use aoc2024_common::scaffold;
+
+const USAGE: &str = "\
+Usage: aoc <command> [args]
+
+Commands:
+  dashboard [--timeout SECS]                Run all days and update the progress table in README.md
+  describe <day>                            Save the puzzle text and its examples (needs AOC_TOKEN)
+  encrypt <day>...                          Write input.txt.enc alongside each day's input.txt
+  gen <day> [--size N] [--seed S] [--k K]   Print a random input for the given day
+  leaderboard <id>                          Show a private leaderboard (needs AOC_TOKEN)
+  new-day <day>                             Create dayNN/ from the template (run from the workspace root)
+  new-year <year> <dir>                     Create an empty workspace for another year's event
+  run [<day>...] [--timeout SECS]           Run built days with a time limit each (default: all)
+  submit <day> <part> <answer>              Submit an answer (needs AOC_TOKEN)";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some(command) = args.first() else {
+        eprintln!("{}", USAGE);
+        exit(1);
+    };
+
+    let res = match command.as_str() {
+        "dashboard" => cmd_dashboard(&args[1..]),
+        "describe" => cmd_describe(&args[1..]),
+        "encrypt" => cmd_encrypt(&args[1..]),
+        "gen" => cmd_gen(&args[1..]),
+        "leaderboard" => cmd_leaderboard(&args[1..]),
+        "new-day" => cmd_new_day(&args[1..]),
+        "new-year" => cmd_new_year(&args[1..]),
+        "run" => cmd_run(&args[1..]),
+        "submit" => cmd_submit(&args[1..]),
+        _ => Err(format!("Unknown command '{}'", command)),
+    };
+
+    if let Err(e) = res {
+        eprintln!("{}\n\n{}", e, USAGE);
+        exit(1);
+    }
+}
+
+fn cmd_dashboard(args: &[String]) -> Result<(), String> {
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            // read again per day by run::timeout_for_day, this just validates it
+            "--timeout" => _ = parse_value::<f64>(arg, it.next())?,
+            _ => return Err(format!("Unknown argument '{}'", arg)),
+        }
+    }
+
+    let table = dashboard::format_table(&dashboard::collect_stats());
+    dashboard::update_readme(&table)?;
+    print!("{}", table);
+    println!("\nUpdated README.md");
+    Ok(())
+}
+
+fn cmd_describe(args: &[String]) -> Result<(), String> {
+    let day = parse_day(args.first())?;
+    for path in describe::fetch_description(day).map_err(|e| e.to_string())? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn cmd_encrypt(args: &[String]) -> Result<(), String> {
+    if args.is_empty() {
+        return Err("Missing day".to_string());
+    }
+    for arg in args {
+        let path = file::encrypt_input(parse_day(Some(arg))?)?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn cmd_gen(args: &[String]) -> Result<(), String> {
+    let day = parse_day(args.first())?;
+    let mut params = GenParams::default();
+    let mut it = args[1..].iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--size" => params.size = parse_value(arg, it.next())?,
+            "--seed" => params.seed = parse_value(arg, it.next())?,
+            "--k" => params.k = parse_value(arg, it.next())?,
+            _ => return Err(format!("Unknown argument '{}'", arg)),
+        }
+    }
+
+    match gen::generate(day, &params) {
+        Some(input) => {
+            print!("{}", input);
+            Ok(())
+        }
+        None => Err(format!(
+            "No generator for day {} (available: {:?})",
+            day,
+            gen::GENERATOR_DAYS,
+        )),
+    }
+}
+
+fn cmd_leaderboard(args: &[String]) -> Result<(), String> {
+    let id = args.first().ok_or("Missing leaderboard ID")?
+        .parse::<u64>().map_err(|_| "Invalid leaderboard ID")?;
+    let board = leaderboard::get_leaderboard(id).map_err(|e| e.to_string())?;
+    print!("{}", leaderboard::format_report(&board));
+    Ok(())
+}
+
+fn cmd_new_day(args: &[String]) -> Result<(), String> {
+    let day = parse_day(args.first())?;
+    for path in scaffold::new_day(&PathBuf::from("."), day)? {
+        println!("Wrote {}", path.display());
+    }
+    println!("Run `aoc describe {}` to fetch the puzzle text and examples", day);
+    Ok(())
+}
+
+fn cmd_new_year(args: &[String]) -> Result<(), String> {
+    let year = parse_value::<u32>("year", args.first())?;
+    let dir = args.get(1).ok_or("Missing directory")?;
+    for path in scaffold::new_year(&PathBuf::from(dir), year)? {
+        println!("Wrote {}", path.display());
+    }
+    println!("Run `aoc new-day 1` from {} to add the first day", dir);
+    Ok(())
+}
+
+fn cmd_run(args: &[String]) -> Result<(), String> {
+    let mut days = Vec::new();
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            // read again per day by run::timeout_for_day, this just validates it
+            "--timeout" => _ = parse_value::<f64>(arg, it.next())?,
+            _ => days.push(parse_day(Some(arg))?),
+        }
+    }
+    if days.is_empty() {
+        days = (1..=25).filter(|&day| run::day_binary(day).is_some()).collect();
+        if days.is_empty() {
+            return Err("No day binaries found; build them first with `cargo build --release`".to_string());
+        }
+    }
+
+    println!("{}", run::format_header());
+    let mut all_ok = true;
+    for day in days {
+        let result = run::run_day(day, run::timeout_for_day(day));
+        all_ok &= result.status == Status::Ok;
+        println!("{}", run::format_row(&result));
+    }
+    if !all_ok {
+        exit(1);
+    }
+    Ok(())
+}
+
+fn cmd_submit(args: &[String]) -> Result<(), String> {
+    let day = parse_day(args.first())?;
+    let part = match args.get(1).map(|s| s.as_str()) {
+        Some("1") => 1,
+        Some("2") => 2,
+        _ => return Err("Part must be 1 or 2".to_string()),
+    };
+    let answer = args.get(2).ok_or("Missing answer")?;
+
+    match net::submit_answer(day, part, answer).map_err(|e| e.to_string())? {
+        SubmitOutcome::Correct => {
+            println!("Correct!");
+            let path = file::record_answer(day, part, answer, file::local_input_hash(day).as_deref())?;
+            println!("Recorded answer in {}", path.display());
+        }
+        SubmitOutcome::Incorrect => println!("Incorrect"),
+        SubmitOutcome::TooSoon => println!("Answered too recently, try again later"),
+        SubmitOutcome::WrongLevel => println!("Wrong level (part already solved or locked)"),
+        SubmitOutcome::Unknown(body) => println!("Unrecognized response:\n{}", body),
+    }
+    Ok(())
+}
+
+fn parse_day(arg: Option<&String>) -> Result<u32, String> {
+    let arg = arg.ok_or("Missing day")?;
+    match arg.trim_start_matches("day").parse::<u32>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("Invalid day '{}'", arg)),
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<&String>) -> Result<T, String> {
+    value
+        .ok_or(format!("Missing value for {}", flag))?
+        .parse::<T>()
+        .map_err(|_| format!("Invalid value for {}", flag))
+}
@@ -0,0 +1,77 @@
+use std::fmt::{Display, Formatter};
+use std::process::exit;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use crate::{args, config};
+
+/// Exit code of a day that stopped because its budget ran out (the same one coreutils `timeout`
+/// uses), so that runners can tell it apart from a crash.
+pub const TIMED_OUT_EXIT_CODE: i32 = 124;
+
+static START: OnceLock<Instant> = OnceLock::new();
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Exhausted {
+    Time(Duration),
+    Steps(u64),
+}
+
+impl Display for Exhausted {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Exhausted::Time(limit) => write!(f, "timed out after {:.1}s", limit.as_secs_f64()),
+            Exhausted::Steps(limit) => write!(f, "gave up after {} steps", limit),
+        }
+    }
+}
+
+/// Wall-clock budget for a single day, for long-running solutions to check cooperatively.
+#[derive(Clone, Copy, Debug)]
+pub struct Budget {
+    limit: Option<Duration>,
+    deadline: Option<Instant>,
+}
+
+impl Budget {
+    /// The budget for `day`: `--timeout SECS` on the command line, else `timeout` in aoc.toml,
+    /// else unlimited. Measured from the first time any budget is created.
+    pub fn for_day(day: u32) -> Self {
+        let secs = match args::value("--timeout") {
+            Some(s) => s.parse::<f64>().unwrap_or_else(|_| panic!("Invalid value for --timeout: {}", s)),
+            None => config::get(day, "timeout", 0.0),
+        };
+        if secs <= 0.0 {
+            return Self::unlimited();
+        }
+        let limit = Duration::from_secs_f64(secs);
+        let start = *START.get_or_init(Instant::now);
+        Self { limit: Some(limit), deadline: Some(start + limit) }
+    }
+
+    pub fn unlimited() -> Self {
+        Self { limit: None, deadline: None }
+    }
+
+    pub fn check(&self) -> Result<(), Exhausted> {
+        match (self.limit, self.deadline) {
+            (Some(limit), Some(deadline)) if Instant::now() >= deadline => Err(Exhausted::Time(limit)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Time left before the deadline, or `None` if unlimited.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    pub fn limit(&self) -> Option<Duration> {
+        self.limit
+    }
+}
+
+/// Stops the day without panicking: reports how far it got on stderr and exits with
+/// [`TIMED_OUT_EXIT_CODE`]. Answers already printed to stdout are left as they are.
+pub fn give_up(reason: &Exhausted, progress: &str) -> ! {
+    eprintln!("{} ({})", reason, progress);
+    exit(TIMED_OUT_EXIT_CODE);
+}
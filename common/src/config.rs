@@ -0,0 +1,80 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use toml::{Table, Value};
+use crate::args;
+
+const CONFIG_FILE_NAME: &str = "aoc.toml";
+const DEFAULT_YEAR: u32 = 2024;
+
+static CONFIG: OnceLock<Table> = OnceLock::new();
+
+/// Looks up a puzzle parameter for `day`. In order of precedence: `--set key=value` on the
+/// command line, the `[dayNN.example]` table of `aoc.toml` when running with `--example`, the
+/// `[dayNN]` table, then `default`.
+pub fn get<T: FromStr>(day: u32, key: &str, default: T) -> T {
+    match lookup(day, key) {
+        Some(s) => s.parse::<T>().unwrap_or_else(|_| panic!("Invalid value for {}: {}", key, s)),
+        None => default,
+    }
+}
+
+/// The event year, from the top-level `year` key of `aoc.toml` so that a new year's workspace can
+/// reuse this crate as-is.
+pub fn year() -> u32 {
+    match load().get("year") {
+        Some(Value::Integer(year)) => u32::try_from(*year).expect("Invalid year in config"),
+        Some(_) => panic!("Invalid year in config"),
+        None => DEFAULT_YEAR,
+    }
+}
+
+fn lookup(day: u32, key: &str) -> Option<String> {
+    let cli_override = args::values("--set").into_iter()
+        .rev()
+        .filter_map(|kv| kv.split_once('=').map(|(k, v)| (k.trim().to_string(), v.trim().to_string())))
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v);
+    if cli_override.is_some() {
+        return cli_override;
+    }
+
+    let day_table = load().get(&format!("day{:02}", day))?.as_table()?;
+    let example_val = args::value("--example")
+        .and(day_table.get("example"))
+        .and_then(|t| t.as_table())
+        .and_then(|t| t.get(key));
+    example_val.or(day_table.get(key)).and_then(value_to_string)
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Integer(i) => Some(i.to_string()),
+        Value::Float(f) => Some(f.to_string()),
+        Value::Boolean(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn load() -> &'static Table {
+    CONFIG.get_or_init(|| {
+        let Some(path) = find_config_file() else { return Table::new(); };
+        let content = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e));
+        content.parse::<Table>()
+            .unwrap_or_else(|e| panic!("Failed to parse {}: {}", path.display(), e))
+    })
+}
+
+// days are run either from the workspace root or from their own directory
+fn find_config_file() -> Option<PathBuf> {
+    if let Ok(path) = env::var("AOC_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    [PathBuf::from(".").join(CONFIG_FILE_NAME), PathBuf::from("..").join(CONFIG_FILE_NAME)]
+        .into_iter()
+        .find(|path| path.exists())
+}
@@ -0,0 +1,82 @@
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::PathBuf;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use sha2::{Digest, Sha256};
+
+const MAGIC: &[u8] = b"AOCENC1\n";
+const NONCE_LEN: usize = 12;
+const KEY_FILE_NAME: &str = ".aoc-key";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CryptError {
+    MissingKey,
+    NotEncrypted,
+    Corrupt,
+}
+
+impl Display for CryptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptError::MissingKey => write!(
+                f,
+                "No input key found (set AOC_INPUT_KEY, AOC_INPUT_KEYFILE or create {})",
+                KEY_FILE_NAME,
+            ),
+            CryptError::NotEncrypted => write!(f, "File is not an encrypted input"),
+            CryptError::Corrupt => write!(f, "Decryption failed (wrong key or corrupted file)"),
+        }
+    }
+}
+
+/// Encrypts with ChaCha20-Poly1305 under a fresh random nonce. The output is the magic header,
+/// the nonce and then the ciphertext.
+pub fn encrypt(plaintext: &[u8]) -> Result<Vec<u8>, CryptError> {
+    let cipher = ChaCha20Poly1305::new(&load_key()?);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher.encrypt(&nonce, plaintext).map_err(|_| CryptError::Corrupt)?;
+
+    let mut out = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+pub fn decrypt(data: &[u8]) -> Result<Vec<u8>, CryptError> {
+    let body = data.strip_prefix(MAGIC).ok_or(CryptError::NotEncrypted)?;
+    if body.len() < NONCE_LEN {
+        return Err(CryptError::Corrupt);
+    }
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+    ChaCha20Poly1305::new(&load_key()?)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| CryptError::Corrupt)
+}
+
+// the key material is hashed so that any passphrase works, not just exactly 32 bytes
+fn load_key() -> Result<Key, CryptError> {
+    let material = match env::var("AOC_INPUT_KEY") {
+        Ok(key) => key,
+        Err(_) => find_key_file()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .ok_or(CryptError::MissingKey)?,
+    };
+    let material = material.trim();
+    if material.is_empty() {
+        return Err(CryptError::MissingKey);
+    }
+    Ok(Key::clone_from_slice(&Sha256::digest(material.as_bytes())))
+}
+
+// days are run either from the workspace root or from their own directory
+fn find_key_file() -> Option<PathBuf> {
+    if let Ok(path) = env::var("AOC_INPUT_KEYFILE") {
+        return Some(PathBuf::from(path));
+    }
+    [PathBuf::from(".").join(KEY_FILE_NAME), PathBuf::from("..").join(KEY_FILE_NAME)]
+        .into_iter()
+        .find(|path| path.exists())
+}
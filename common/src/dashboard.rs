@@ -0,0 +1,128 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use crate::file::{get_day_dir, read_answers, RecordedAnswers};
+use crate::run::{self, DayRun, Status};
+
+const README_FILE_NAME: &str = "README.md";
+const START_MARKER: &str = "<!-- dashboard:start -->";
+const END_MARKER: &str = "<!-- dashboard:end -->";
+
+// parts slower than this get called out in the table
+const SLOW_THRESHOLD: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug)]
+pub struct DayStats {
+    pub day: u32,
+    /// Answers accepted by the site, from the day's answers store.
+    pub recorded: RecordedAnswers,
+    pub run: DayRun,
+    pub loc: usize,
+}
+
+/// Runs every day that exists in the workspace and gathers what the dashboard shows about it.
+pub fn collect_stats() -> Vec<DayStats> {
+    (1..=25)
+        .filter(|&day| get_day_dir(day) != Path::new("."))
+        .map(|day| DayStats {
+            day,
+            recorded: read_answers(day),
+            run: run::run_day(day, run::timeout_for_day(day)),
+            loc: count_loc(&get_day_dir(day)),
+        })
+        .collect()
+}
+
+pub fn format_table(stats: &[DayStats]) -> String {
+    let stars: usize = stats.iter().filter(|s| !is_stale(s)).map(|s| s.recorded.answers.len()).sum();
+    let total_loc: usize = stats.iter().map(|s| s.loc).sum();
+
+    let mut out = format!("**{}/{}** stars, {} lines of code\n\n", stars, stats.len() * 2, total_loc);
+    out += "| Day | Stars | Part 1 | Part 2 | LOC |\n";
+    out += "|----:|:-----:|-------:|-------:|----:|\n";
+    for s in stats {
+        out += &format!(
+            "| {} | {} | {} | {} | {} |\n",
+            s.day,
+            format_stars(s),
+            format_part(s, 1),
+            format_part(s, 2),
+            s.loc,
+        );
+    }
+    out += &format!(
+        "\n★ solved, ☆ unsolved, ✗ no longer matches the recorded answer, ⚠ answer was recorded for \
+        a different input and isn't counted. Parts slower than {}s are in bold.\n",
+        SLOW_THRESHOLD.as_secs(),
+    );
+    out
+}
+
+/// Replaces the dashboard section of the README, appending one if it doesn't have one yet.
+pub fn update_readme(table: &str) -> Result<(), String> {
+    let path = Path::new(".").join(README_FILE_NAME);
+    let readme = fs::read_to_string(&path).unwrap_or_default();
+    let section = format!("{}\n{}{}", START_MARKER, table, END_MARKER);
+
+    let updated = match (readme.find(START_MARKER), readme.find(END_MARKER)) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{}{}", &readme[..start], section, &readme[end + END_MARKER.len()..])
+        }
+        _ => format!("{}\n\n## Progress\n\n{}\n", readme.trim_end(), section),
+    };
+    fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// answers recorded for another input say nothing about whether this one's are right
+fn is_stale(stats: &DayStats) -> bool {
+    stats.run.input_hash.as_deref().is_some_and(|hash| stats.recorded.is_stale_for(hash))
+}
+
+fn format_stars(stats: &DayStats) -> String {
+    let stale = is_stale(stats);
+    (1..=2u8)
+        .map(|part| match (stats.recorded.answers.get(&part), stats.run.answers.get(&part)) {
+            (Some(_), _) if stale => "⚠",
+            (Some(expected), Some(actual)) if expected != actual => "✗",
+            (Some(_), _) => "★",
+            (None, _) => "☆",
+        })
+        .collect()
+}
+
+fn format_part(stats: &DayStats, part: u8) -> String {
+    match (&stats.run.status, stats.run.part_times.get(&part)) {
+        (_, Some(&time)) if time >= SLOW_THRESHOLD => format!("**{}**", format_duration(time)),
+        (_, Some(&time)) => format_duration(time),
+        (Status::TimedOut(_), None) => "timed out".to_string(),
+        (Status::Failed(_), None) => "failed".to_string(),
+        (Status::NotBuilt, None) => "not built".to_string(),
+        (Status::Ok, None) => "-".to_string(),
+    }
+}
+
+fn format_duration(time: Duration) -> String {
+    let secs = time.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{:.2} s", secs)
+    } else {
+        format!("{:.1} ms", secs * 1000.0)
+    }
+}
+
+// non-blank, non-comment lines across the day's source files
+fn count_loc(dir: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries.flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+        .map(|content| {
+            content.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with("//"))
+                .count()
+        })
+        .sum()
+}
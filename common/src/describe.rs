@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use crate::file::{format_answers, get_day_dir};
+use crate::net::{self, NetError};
+
+const ARTICLE_OPEN: &str = "<article class=\"day-desc\">";
+const ARTICLE_CLOSE: &str = "</article>";
+
+#[derive(Clone, Debug, Default)]
+pub struct Example {
+    pub input: String,
+    pub answers: BTreeMap<u8, String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Description {
+    pub markdown: String,
+    pub examples: Vec<Example>,
+}
+
+/// Fetches the puzzle page and writes `description.md`, `exampleN.txt` and `exampleN.answers`
+/// into the day's directory. Returns the paths written.
+pub fn fetch_description(day: u32) -> Result<Vec<PathBuf>, NetError> {
+    let desc = parse_puzzle_page(&net::download_puzzle_page(day)?);
+    let dir = get_day_dir(day);
+
+    let mut written = Vec::new();
+    let mut write = |name: String, content: &str| {
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap_or_else(|e| panic!("Failed to write {}: {}", path.display(), e));
+        written.push(path);
+    };
+
+    write("description.md".to_string(), &desc.markdown);
+    for (i, example) in desc.examples.iter().enumerate() {
+        write(format!("example{}.txt", i + 1), &example.input);
+        if !example.answers.is_empty() {
+            write(format!("example{}.answers", i + 1), &format_answers(&example.answers));
+        }
+    }
+    Ok(written)
+}
+
+/// Converts the puzzle articles on the page to Markdown and pulls out the example blocks. The
+/// answer for an example is taken to be the last emphasized code span following it, which is how
+/// the puzzle text states it; a part 2 article without its own example contributes its answer to
+/// the last example of part 1.
+pub fn parse_puzzle_page(html: &str) -> Description {
+    let mut desc = Description::default();
+    let mut rest = html;
+    let mut part = 0;
+    while let Some(start) = rest.find(ARTICLE_OPEN) {
+        let body_start = start + ARTICLE_OPEN.len();
+        let body_end = rest[body_start..].find(ARTICLE_CLOSE).map(|i| body_start + i).unwrap_or(rest.len());
+        let tokens = tokenize(&rest[body_start..body_end]);
+        part += 1;
+
+        if !desc.markdown.is_empty() {
+            desc.markdown += "\n";
+        }
+        desc.markdown += &to_markdown(&tokens);
+        collect_examples(&tokens, part, &mut desc.examples);
+
+        rest = &rest[(body_end + ARTICLE_CLOSE.len()).min(rest.len())..];
+    }
+    desc
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Open(String, Option<String>),
+    Close(String),
+    Text(String),
+}
+
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(tag_body) = rest.strip_prefix('<') {
+            let end = tag_body.find('>').unwrap_or(tag_body.len());
+            let tag = &tag_body[..end];
+            rest = &tag_body[(end + 1).min(tag_body.len())..];
+            if let Some(name) = tag.strip_prefix('/') {
+                tokens.push(Token::Close(name.trim().to_ascii_lowercase()));
+            } else {
+                let name = tag.split_whitespace().next().unwrap_or_default().trim_end_matches('/');
+                tokens.push(Token::Open(name.to_ascii_lowercase(), get_attr(tag, "href")));
+            }
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(Token::Text(decode_entities(&rest[..end])));
+            rest = &rest[end..];
+        }
+    }
+    tokens
+}
+
+fn get_attr(tag: &str, attr: &str) -> Option<String> {
+    let needle = format!("{}=\"", attr);
+    let start = tag.find(&needle)? + needle.len();
+    let end = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Matches `<code><em>x</em></code>` or `<em><code>x</code></em>` at the start of `tokens`,
+/// returning the text and the number of tokens consumed.
+fn match_emphasized_code(tokens: &[Token]) -> Option<(String, usize)> {
+    let open = |t: &Token, n: &str| matches!(t, Token::Open(name, _) if name == n);
+    let close = |t: &Token, n: &str| matches!(t, Token::Close(name) if name == n);
+    match tokens {
+        [a, b, Token::Text(text), c, d, ..] if (open(a, "code") && open(b, "em") && close(c, "em") && close(d, "code"))
+            || (open(a, "em") && open(b, "code") && close(c, "code") && close(d, "em")) => {
+            Some((text.clone(), 5))
+        }
+        _ => None,
+    }
+}
+
+fn to_markdown(tokens: &[Token]) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut links = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if !in_pre {
+            if let Some((text, consumed)) = match_emphasized_code(&tokens[i..]) {
+                out += &format!("**`{}`**", text);
+                i += consumed;
+                continue;
+            }
+        }
+        match &tokens[i] {
+            Token::Text(text) => out += text,
+            Token::Open(name, href) => match name.as_str() {
+                "pre" => {
+                    in_pre = true;
+                    out += "```\n";
+                }
+                _ if in_pre => {}
+                "h2" => out += "## ",
+                "code" => out += "`",
+                "em" => out += "*",
+                "li" => out += "- ",
+                "a" => {
+                    links.push(href.clone().unwrap_or_default());
+                    out += "[";
+                }
+                _ => {}
+            },
+            Token::Close(name) => match name.as_str() {
+                "pre" => {
+                    in_pre = false;
+                    if !out.ends_with('\n') {
+                        out += "\n";
+                    }
+                    out += "```\n\n";
+                }
+                _ if in_pre => {}
+                "h2" | "p" | "ul" => out += "\n\n",
+                "code" => out += "`",
+                "em" => out += "*",
+                "li" => out += "\n",
+                "a" => out += &format!("]({})", links.pop().unwrap_or_default()),
+                _ => {}
+            },
+        }
+        i += 1;
+    }
+
+    while out.contains("\n\n\n") {
+        out = out.replace("\n\n\n", "\n\n");
+    }
+    out.trim().to_string() + "\n"
+}
+
+fn collect_examples(tokens: &[Token], part: u8, examples: &mut Vec<Example>) {
+    let first_own_example = examples.len();
+    let mut in_pre = false;
+    let mut cur_block = String::new();
+    // answer stated before this article's first example, which belongs to the previous article's
+    // last example if this article turns out not to have one of its own
+    let mut carried_answer = None;
+    let mut i = 0;
+    while i < tokens.len() {
+        if !in_pre {
+            if let Some((text, consumed)) = match_emphasized_code(&tokens[i..]) {
+                if examples.len() > first_own_example {
+                    examples.last_mut().unwrap().answers.insert(part, text);
+                } else {
+                    carried_answer = Some(text);
+                }
+                i += consumed;
+                continue;
+            }
+        }
+        match &tokens[i] {
+            Token::Open(name, _) if name == "pre" => {
+                in_pre = true;
+                cur_block.clear();
+            }
+            Token::Close(name) if name == "pre" => {
+                in_pre = false;
+                examples.push(Example { input: cur_block.clone(), answers: BTreeMap::new() });
+            }
+            Token::Text(text) if in_pre => cur_block += text,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if examples.len() == first_own_example {
+        if let (Some(answer), Some(example)) = (carried_answer, examples.last_mut()) {
+            example.answers.insert(part, answer);
+        }
+    }
+}
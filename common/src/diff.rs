@@ -0,0 +1,216 @@
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+use std::process::exit;
+use std::sync::Once;
+use crate::args;
+use crate::gen::{self, GenParams};
+
+const DEFAULT_CASES: usize = 200;
+const MINIMIZE_SEEDS_PER_SIZE: u64 = 200;
+
+/// Settings for a differential run, read from `--diff [--cases N] [--size N] [--seed S] [--k K]`.
+#[derive(Clone, Copy, Debug)]
+pub struct DiffConfig {
+    pub cases: usize,
+    pub params: GenParams,
+}
+
+impl DiffConfig {
+    pub fn from_args() -> Option<Self> {
+        if !args::flag("--diff") {
+            return None;
+        }
+        let defaults = GenParams::default();
+        Some(Self {
+            cases: args::value_or("--cases", DEFAULT_CASES),
+            params: GenParams {
+                size: args::value_or("--size", defaults.size),
+                seed: args::value_or("--seed", defaults.seed),
+                k: args::value_or("--k", defaults.k),
+            },
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome<T> {
+    Value(T),
+    Panicked(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct Disagreement<T> {
+    /// Generator parameters of the first failing case, before minimization.
+    pub found_at: GenParams,
+    pub input: String,
+    pub reference: Outcome<T>,
+    pub optimized: Outcome<T>,
+}
+
+/// Runs `reference` and `optimized` against `cfg.cases` generated inputs for `day` and returns
+/// the first case on which they disagree, shrunk as far as possible while still disagreeing.
+/// Inputs on which the reference itself panics are treated as invalid and skipped.
+pub fn find_disagreement<T, R, O>(day: u32, cfg: &DiffConfig, reference: R, optimized: O) -> Option<Disagreement<T>>
+where
+    T: PartialEq,
+    R: Fn(&str) -> T,
+    O: Fn(&str) -> T,
+{
+    let check = |input: &str| -> Option<(Outcome<T>, Outcome<T>)> {
+        let ref_res = evaluate(&reference, input);
+        if matches!(ref_res, Outcome::Panicked(_)) {
+            return None;
+        }
+        let opt_res = evaluate(&optimized, input);
+        if ref_res == opt_res {
+            None
+        } else {
+            Some((ref_res, opt_res))
+        }
+    };
+
+    let mut found = None;
+    for i in 0..cfg.cases as u64 {
+        let params = GenParams { seed: cfg.params.seed.wrapping_add(i), ..cfg.params };
+        let input = gen::generate(day, &params).unwrap_or_else(|| panic!("No generator for day {}", day));
+        if check(&input).is_some() {
+            found = Some((params, input));
+            break;
+        }
+    }
+
+    found.map(|(found_at, input)| {
+        let input = minimize(day, &found_at, input, |s| check(s).is_some());
+        let (reference, optimized) = check(&input).unwrap();
+        Disagreement { found_at, input, reference, optimized }
+    })
+}
+
+
+/// Runs the differential check and prints a report, exiting with a failure status if the two
+/// implementations disagree.
+pub fn run<T, R, O>(day: u32, label: &str, cfg: &DiffConfig, reference: R, optimized: O)
+where
+    T: PartialEq + Debug,
+    R: Fn(&str) -> T,
+    O: Fn(&str) -> T,
+{
+    println!(
+        "Day {} {}: checking {} cases (size {}, seeds {}..{})",
+        day,
+        label,
+        cfg.cases,
+        cfg.params.size,
+        cfg.params.seed,
+        cfg.params.seed.wrapping_add(cfg.cases as u64),
+    );
+    match find_disagreement(day, cfg, reference, optimized) {
+        None => println!("No disagreements found"),
+        Some(d) => {
+            println!(
+                "Disagreement found at seed {} (size {}), minimized input:",
+                d.found_at.seed,
+                d.found_at.size,
+            );
+            println!("{}", d.input.trim_end());
+            println!("Reference: {:?}", d.reference);
+            println!("Optimized: {:?}", d.optimized);
+            exit(1);
+        }
+    }
+}
+
+fn evaluate<T, F: Fn(&str) -> T>(f: &F, input: &str) -> Outcome<T> {
+    let result = {
+        let _quiet = QuietPanics::new();
+        panic::catch_unwind(AssertUnwindSafe(|| f(input)))
+    };
+    match result {
+        Ok(val) => Outcome::Value(val),
+        Err(payload) => Outcome::Panicked(
+            payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default()
+        ),
+    }
+}
+
+/// Keeps panics from being printed while it's alive, since the implementations' panics are caught
+/// and reported as outcomes. Panics anywhere else still go to the usual hook, and dropping the
+/// guard turns it back on even while unwinding.
+struct QuietPanics;
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+impl QuietPanics {
+    fn new() -> Self {
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| {
+            let prev_hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                if !QUIET.with(Cell::get) {
+                    prev_hook(info);
+                }
+            }));
+        });
+        QUIET.with(|quiet| quiet.set(true));
+        Self
+    }
+}
+
+impl Drop for QuietPanics {
+    fn drop(&mut self) {
+        QUIET.with(|quiet| quiet.set(false));
+    }
+}
+
+fn minimize<F: Fn(&str) -> bool>(day: u32, found_at: &GenParams, input: String, is_failing: F) -> String {
+    // first look for the smallest generator size that still fails, since regenerating keeps the
+    // input well-formed
+    let mut best = input;
+    'sizes: for size in 1..found_at.size {
+        for seed in 0..MINIMIZE_SEEDS_PER_SIZE {
+            let params = GenParams { size, seed: found_at.seed.wrapping_add(seed), ..*found_at };
+            let candidate = gen::generate(day, &params).unwrap();
+            if is_failing(&candidate) {
+                best = candidate;
+                break 'sizes;
+            }
+        }
+    }
+
+    if !gen::LINE_SHRINKABLE_DAYS.contains(&day) {
+        return best;
+    }
+
+    // then greedily drop chunks of lines below the header while the disagreement persists
+    let all_lines: Vec<&str> = best.lines().collect();
+    let (header, body) = all_lines.split_at(gen::header_lines(day).min(all_lines.len()));
+    let with_header = |lines: &[&str]| header.iter().chain(lines).copied().collect::<Vec<_>>().join("\n") + "\n";
+    let mut lines = body.to_vec();
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        let mut progressed = false;
+        while start < lines.len() {
+            let candidate_lines: Vec<&str> = lines[..start].iter()
+                .chain(lines[(start + chunk).min(lines.len())..].iter())
+                .copied()
+                .collect();
+            let candidate = with_header(&candidate_lines);
+            if !candidate_lines.is_empty() && is_failing(&candidate) {
+                lines = candidate_lines;
+                progressed = true;
+            } else {
+                start += chunk;
+            }
+        }
+        if !progressed {
+            chunk /= 2;
+        }
+    }
+    with_header(&lines)
+}
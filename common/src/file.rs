@@ -1,18 +1,139 @@
-use std::{env, fs};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+use crate::hash::{input_hash, InputHasher};
+use crate::{args, crypt, multi, net};
 
 const INPUT_FILE_NAME: &str = "input.txt";
+const ENCRYPTED_INPUT_FILE_NAME: &str = "input.txt.enc";
+const ANSWERS_FILE_NAME: &str = "answers.txt";
+
+/// Prefix of the line a day writes to stderr with its input's hash when `AOC_REPORT_INPUT_HASH` is
+/// set, so that runners know which input their results belong to.
+pub const INPUT_HASH_PREFIX: &str = "Input hash: ";
+
+static STALE_WARNING: Once = Once::new();
+
+/// Answers accepted by the site for the real input, as recorded by `aoc submit`, along with the
+/// hash of the input they were for.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RecordedAnswers {
+     pub input_hash: Option<String>,
+     pub answers: BTreeMap<u8, String>,
+}
+
+impl RecordedAnswers {
+     /// Whether these answers were recorded for some other input than the one with `hash`.
+     pub fn is_stale_for(&self, hash: &str) -> bool {
+          self.input_hash.as_deref().is_some_and(|recorded| recorded != hash)
+     }
+}
 
 pub fn read_input_string(day: u32) -> String {
-     if let Ok(file) = try_open_file(day) {
-          let mut content: String = String::new();
-          BufReader::new(file).read_to_string(&mut content).expect("Failed to read input file");
-          content.trim().to_string()
-     } else if let Ok(content) = try_download_input(day) {
-          content.trim().to_string()
+     if let Some(dir) = args::value("--inputs") {
+          multi::run_all_inputs(day, &PathBuf::from(dir));
+     }
+
+     let (content, is_own_input) = if let Some(path) = args::value("--input") {
+          (read_input_file(&PathBuf::from(path)), false)
+     } else if let Some(n) = args::value("--example") {
+          let n = n.parse::<u32>().expect("Invalid example number");
+          (read_example_string(day, n), false)
+     } else if let Some(content) = try_read_local_input(day) {
+          (content, true)
      } else {
-          panic!("Could not open or download input!");
+          match net::download_input(day) {
+               Ok(content) => {
+                    save_downloaded_input(day, &content);
+                    (content.trim().to_string(), true)
+               }
+               Err(e) => panic!("Could not open or download input! ({})", e),
+          }
+     };
+
+     let hash = input_hash(&content);
+     if is_own_input {
+          check_input_hash(day, &hash);
+     }
+     report_input_hash(&hash);
+     content
+}
+
+/// Streams the input rather than loading it whole, for inputs too big to hold as a list of lines.
+/// Plain input files are read straight from disk; examples and encrypted or downloaded inputs are
+/// loaded as usual and read back from memory.
+pub fn read_input_stream(day: u32) -> Box<dyn BufRead> {
+     let (path, is_own_input) = if args::value("--inputs").is_some() || args::value("--example").is_some() {
+          (None, false)
+     } else if let Some(path) = args::value("--input") {
+          let path = PathBuf::from(path);
+          let is_plain = path.extension().is_none_or(|ext| ext != "enc");
+          (is_plain.then_some(path), false)
+     } else {
+          (find_input_file(day, INPUT_FILE_NAME), true)
+     };
+     let Some(path) = path else {
+          return Box::new(Cursor::new(read_input_string(day).into_bytes()));
+     };
+
+     let file = File::open(&path).unwrap_or_else(|e| panic!("Could not open {}: {}", path.display(), e));
+     Box::new(HashingReader::new(BufReader::new(file), day, is_own_input))
+}
+
+/// Hashes the input as it's read, then checks and reports the hash once the end is reached, so
+/// that the file only has to be read once. Nothing is checked if the reader isn't read to the end.
+struct HashingReader<R> {
+     inner: R,
+     hasher: InputHasher,
+     // how much of what `inner` currently has buffered is already hashed
+     hashed_ahead: usize,
+     day: u32,
+     is_own_input: bool,
+     finished: bool,
+}
+
+impl<R: BufRead> HashingReader<R> {
+     fn new(inner: R, day: u32, is_own_input: bool) -> Self {
+          Self { inner, hasher: InputHasher::new(), hashed_ahead: 0, day, is_own_input, finished: false }
+     }
+}
+
+impl<R: BufRead> Read for HashingReader<R> {
+     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+          let available = self.fill_buf()?;
+          let n = available.len().min(buf.len());
+          buf[..n].copy_from_slice(&available[..n]);
+          self.consume(n);
+          Ok(n)
+     }
+}
+
+impl<R: BufRead> BufRead for HashingReader<R> {
+     fn fill_buf(&mut self) -> io::Result<&[u8]> {
+          let buf = self.inner.fill_buf()?;
+          if buf.len() > self.hashed_ahead {
+               self.hasher.update(&buf[self.hashed_ahead..]);
+               self.hashed_ahead = buf.len();
+          }
+          if buf.is_empty() && !self.finished {
+               self.finished = true;
+               let hash = self.hasher.finish();
+               if self.is_own_input {
+                    check_input_hash(self.day, &hash);
+               }
+               report_input_hash(&hash);
+          }
+          Ok(buf)
+     }
+
+     fn consume(&mut self, amt: usize) {
+          self.hashed_ahead = self.hashed_ahead.saturating_sub(amt);
+          self.inner.consume(amt);
      }
 }
 
@@ -24,26 +145,156 @@ pub fn read_input_lines_preserve_blank(day: u32) -> Vec<String> {
      read_input_string(day).split("\n").map(|s| s.to_string()).collect()
 }
 
-fn try_open_file(day: u32) -> Result<File, ()> {
-     if fs::exists(format!("./{}", INPUT_FILE_NAME)).unwrap_or(false) {
-          Ok(File::open(format!("./{}", INPUT_FILE_NAME)).expect("Failed to open input file"))
-     } else if fs::exists(format!("./day{:02}/{}", day, INPUT_FILE_NAME)).unwrap_or(false) {
-          Ok(File::open(format!("./day{:02}/{}", day, INPUT_FILE_NAME)).expect("Failed to open input file"))
+/// Reads an explicitly given input file, decrypting it if it's an `.enc` file.
+pub fn read_input_file(path: &Path) -> String {
+     let data = fs::read(path).unwrap_or_else(|e| panic!("Could not open {}: {}", path.display(), e));
+     let data = if path.extension().is_some_and(|ext| ext == "enc") {
+          crypt::decrypt(&data).unwrap_or_else(|e| panic!("Could not decrypt {}: {}", path.display(), e))
+     } else {
+          data
+     };
+     String::from_utf8(data).expect("Input is not valid UTF-8").trim().to_string()
+}
+
+pub fn read_example_string(day: u32, n: u32) -> String {
+     let path = get_day_dir(day).join(format!("example{}.txt", n));
+     fs::read_to_string(&path)
+          .unwrap_or_else(|_| panic!("Could not open {} (try `aoc describe {}`)", path.display(), day))
+          .trim()
+          .to_string()
+}
+
+/// Expected answers recorded for example `n`, keyed by part.
+pub fn read_example_answers(day: u32, n: u32) -> BTreeMap<u8, String> {
+     fs::read_to_string(get_day_dir(day).join(format!("example{}.answers", n)))
+          .map(|content| parse_answers(&content))
+          .unwrap_or_default()
+}
+
+pub fn read_answers(day: u32) -> RecordedAnswers {
+     let Ok(content) = fs::read_to_string(get_day_dir(day).join(ANSWERS_FILE_NAME)) else {
+          return RecordedAnswers::default();
+     };
+     RecordedAnswers {
+          input_hash: content.lines()
+               .find_map(|line| line.strip_prefix("Input: "))
+               .map(|hash| hash.trim().to_string()),
+          answers: parse_answers(&content),
+     }
+}
+
+/// Records an accepted answer for the input with `input_hash`. Answers recorded for a different
+/// input are dropped rather than kept alongside it.
+pub fn record_answer(day: u32, part: u8, answer: &str, input_hash: Option<&str>) -> Result<PathBuf, String> {
+     let mut recorded = read_answers(day);
+     if let Some(hash) = input_hash {
+          if recorded.is_stale_for(hash) {
+               recorded.answers.clear();
+          }
+          recorded.input_hash = Some(hash.to_string());
+     }
+     recorded.answers.insert(part, answer.trim().to_string());
+
+     let mut content = String::new();
+     if let Some(hash) = &recorded.input_hash {
+          content += &format!("Input: {}\n", hash);
+     }
+     content += &format_answers(&recorded.answers);
+     let path = get_day_dir(day).join(ANSWERS_FILE_NAME);
+     fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+     Ok(path)
+}
+
+/// Hash of the day's own input if it's available locally, without downloading it.
+pub fn local_input_hash(day: u32) -> Option<String> {
+     try_read_local_input(day).map(|content| input_hash(&content))
+}
+
+/// Parses answers in the same `Part N: <answer>` form the days print them in.
+pub fn parse_answers(content: &str) -> BTreeMap<u8, String> {
+     content.lines()
+          .filter_map(|line| line.split_once(": "))
+          .filter_map(|(label, answer)| {
+               let part = label.strip_prefix("Part ")?.parse::<u8>().ok()?;
+               Some((part, answer.trim().to_string()))
+          })
+          .collect()
+}
+
+pub fn format_answers(answers: &BTreeMap<u8, String>) -> String {
+     answers.iter().map(|(part, answer)| format!("Part {}: {}\n", part, answer)).collect()
+}
+
+/// The day's own directory when run from the workspace root, otherwise the current directory.
+pub fn get_day_dir(day: u32) -> PathBuf {
+     let day_dir = PathBuf::from(format!("./day{:02}", day));
+     if fs::exists(&day_dir).unwrap_or(false) {
+          day_dir
      } else {
-          Err(())
-     }
-}
-
-fn try_download_input(day: u32) -> Result<String, ()> {
-     env::var("AOC_TOKEN").map(|token| {
-         let client = reqwest::blocking::Client::new();
-         client
-             .get(format!("https://adventofcode.com/2024/day/{day}/input"))
-             .header("Cookie", format!("session={}", token))
-             .send()
-             .expect("Failed to download input")
-             .text()
-             .expect("Failed to read downloaded input as string")
-     })
-         .map_err(|_| ())
+          PathBuf::from(".")
+     }
+}
+
+fn try_read_local_input(day: u32) -> Option<String> {
+     if let Some(path) = find_input_file(day, INPUT_FILE_NAME) {
+          let mut content: String = String::new();
+          BufReader::new(File::open(path).expect("Failed to open input file"))
+               .read_to_string(&mut content)
+               .expect("Failed to read input file");
+          Some(content.trim().to_string())
+     } else {
+          try_read_encrypted(day).map(|content| content.trim().to_string())
+     }
+}
+
+fn report_input_hash(hash: &str) {
+     if env::var("AOC_REPORT_INPUT_HASH").is_ok() {
+          eprintln!("{}{}", INPUT_HASH_PREFIX, hash);
+     }
+}
+
+// recorded answers belong to whatever input was in place when they were submitted
+fn check_input_hash(day: u32, hash: &str) {
+     let recorded = read_answers(day);
+     if recorded.is_stale_for(hash) {
+          STALE_WARNING.call_once(|| eprintln!(
+               "Warning: input hash {} doesn't match {} recorded in {}, so the recorded answers are stale",
+               hash,
+               recorded.input_hash.as_deref().unwrap_or_default(),
+               ANSWERS_FILE_NAME,
+          ));
+     }
+}
+
+fn find_input_file(day: u32, file_name: &str) -> Option<PathBuf> {
+     [PathBuf::from(".").join(file_name), PathBuf::from(format!("./day{:02}", day)).join(file_name)]
+          .into_iter()
+          .find(|path| fs::exists(path).unwrap_or(false))
+}
+
+fn try_read_encrypted(day: u32) -> Option<String> {
+     let path = find_input_file(day, ENCRYPTED_INPUT_FILE_NAME)?;
+     let data = fs::read(&path).expect("Failed to read encrypted input file");
+     let plaintext = crypt::decrypt(&data)
+          .unwrap_or_else(|e| panic!("Could not decrypt {}: {}", path.display(), e));
+     Some(String::from_utf8(plaintext).expect("Decrypted input is not valid UTF-8"))
+}
+
+/// Writes an encrypted copy of the day's plaintext input next to it, returning its path.
+pub fn encrypt_input(day: u32) -> Result<PathBuf, String> {
+     let path = find_input_file(day, INPUT_FILE_NAME)
+          .ok_or(format!("No {} found for day {}", INPUT_FILE_NAME, day))?;
+     let plaintext = fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+     let encrypted = crypt::encrypt(&plaintext).map_err(|e| e.to_string())?;
+     let out_path = path.with_file_name(ENCRYPTED_INPUT_FILE_NAME);
+     fs::write(&out_path, encrypted).map_err(|e| format!("Failed to write {}: {}", out_path.display(), e))?;
+     Ok(out_path)
+}
+
+// keep a copy of downloaded input so we only ever fetch it once
+fn save_downloaded_input(day: u32, content: &str) {
+     let path = get_day_dir(day).join(INPUT_FILE_NAME);
+     if let Err(e) = fs::write(&path, content) {
+          eprintln!("Failed to save downloaded input to {}: {}", path.display(), e);
+     }
 }
@@ -0,0 +1,12 @@
+use crate::gen::Rng;
+
+pub fn generate(rng: &mut Rng, lines: usize) -> String {
+    let left: Vec<i64> = (0..lines).map(|_| rng.range(10000, 99999)).collect();
+    let mut out = String::new();
+    for &a in &left {
+        // reuse left-hand IDs often enough that the similarity score isn't always zero
+        let b = if rng.chance(40) { *rng.choose(&left) } else { rng.range(10000, 99999) };
+        out += &format!("{}   {}\n", a, b);
+    }
+    out
+}
@@ -0,0 +1,23 @@
+use crate::gen::Rng;
+
+pub fn generate(rng: &mut Rng, reports: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..reports {
+        let len = rng.range(5, 8) as usize;
+        let dir = if rng.chance(50) { 1 } else { -1 };
+        let mut levels = vec![rng.range(20, 80)];
+        for _ in 1..len {
+            // mostly well-behaved steps, with the occasional zero, jump or reversal
+            let step = match rng.below(10) {
+                0 => 0,
+                1 => dir * rng.range(4, 7),
+                2 => -dir * rng.range(1, 3),
+                _ => dir * rng.range(1, 3),
+            };
+            levels.push(levels.last().unwrap() + step);
+        }
+        out += &levels.iter().map(|l| l.to_string()).collect::<Vec<_>>().join(" ");
+        out += "\n";
+    }
+    out
+}
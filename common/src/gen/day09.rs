@@ -0,0 +1,13 @@
+use crate::gen::Rng;
+
+pub fn generate(rng: &mut Rng, files: usize) -> String {
+    let mut out = String::new();
+    for i in 0..files.max(1) {
+        if i > 0 {
+            out.push(char::from(b'0' + rng.range(0, 9) as u8));
+        }
+        out.push(char::from(b'0' + rng.range(1, 9) as u8));
+    }
+    out += "\n";
+    out
+}
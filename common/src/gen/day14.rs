@@ -0,0 +1,18 @@
+use crate::gen::Rng;
+
+const WIDTH: i64 = 101;
+const HEIGHT: i64 = 103;
+
+pub fn generate(rng: &mut Rng, robots: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..robots {
+        out += &format!(
+            "p={},{} v={},{}\n",
+            rng.range(0, WIDTH - 1),
+            rng.range(0, HEIGHT - 1),
+            rng.range(-WIDTH + 1, WIDTH - 1),
+            rng.range(-HEIGHT + 1, HEIGHT - 1),
+        );
+    }
+    out
+}
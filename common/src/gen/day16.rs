@@ -0,0 +1,26 @@
+use crate::gen::{maze, Rng};
+
+/// Generates a reindeer maze with `S` in the bottom-left and `E` in the top-right corner. The
+/// base maze has a single corridor between any two tiles; `loops` extra interior walls are then
+/// knocked out so that there can be more than one best path.
+pub fn generate(rng: &mut Rng, cells: usize, loops: usize) -> String {
+    let mut grid = maze::carve(rng, cells);
+    let dim = grid.len();
+
+    let mut knocked = 0;
+    let mut attempts = 0;
+    while knocked < loops && attempts < loops * 100 {
+        attempts += 1;
+        let x = rng.range(1, dim as i64 - 2) as usize;
+        let y = rng.range(1, dim as i64 - 2) as usize;
+        // only walls sitting between two cells, i.e. with exactly one odd coordinate
+        if grid[y][x] == b'#' && (x % 2 == 1) != (y % 2 == 1) {
+            grid[y][x] = b'.';
+            knocked += 1;
+        }
+    }
+
+    grid[dim - 2][1] = b'S';
+    grid[1][dim - 2] = b'E';
+    maze::render(&grid)
+}
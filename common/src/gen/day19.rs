@@ -0,0 +1,35 @@
+use crate::gen::Rng;
+
+const STRIPES: &[u8] = b"wubrg";
+
+pub fn generate(rng: &mut Rng, towels: usize) -> String {
+    let mut patterns: Vec<String> = Vec::new();
+    while patterns.len() < towels.clamp(3, 400) {
+        let len = rng.range(1, 6) as usize;
+        let pattern = random_stripes(rng, len);
+        if !patterns.contains(&pattern) {
+            patterns.push(pattern);
+        }
+    }
+
+    let mut out = patterns.join(", ") + "\n\n";
+    for _ in 0..towels {
+        let target_len = rng.range(8, 40) as usize;
+        let mut towel = String::new();
+        while towel.len() < target_len {
+            // build mostly from real patterns so a good share of towels are possible
+            if rng.chance(90) {
+                towel += rng.choose(&patterns).as_str();
+            } else {
+                towel += &random_stripes(rng, 1);
+            }
+        }
+        out += &towel;
+        out += "\n";
+    }
+    out
+}
+
+fn random_stripes(rng: &mut Rng, len: usize) -> String {
+    (0..len).map(|_| char::from(*rng.choose(STRIPES))).collect()
+}
@@ -0,0 +1,24 @@
+use crate::gen::{maze, Rng};
+
+/// Generates a race track: the path between two random cells of a perfect maze, with every tile
+/// off that path walled in so that the track is a single corridor from `S` to `E`.
+pub fn generate(rng: &mut Rng, cells: usize) -> String {
+    let maze_grid = maze::carve(rng, cells);
+    let dim = maze_grid.len();
+    let cells = (dim - 1) / 2;
+
+    let pick_cell = |rng: &mut Rng| (rng.below(cells) * 2 + 1, rng.below(cells) * 2 + 1);
+    let start = pick_cell(rng);
+    let mut end = pick_cell(rng);
+    while end == start {
+        end = pick_cell(rng);
+    }
+
+    let mut grid = vec![vec![b'#'; dim]; dim];
+    for (x, y) in maze::find_path(&maze_grid, start, end) {
+        grid[y][x] = b'.';
+    }
+    grid[start.1][start.0] = b'S';
+    grid[end.1][end.0] = b'E';
+    maze::render(&grid)
+}
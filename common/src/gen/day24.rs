@@ -0,0 +1,103 @@
+use std::collections::{HashMap, HashSet};
+use crate::gen::Rng;
+
+struct Gate {
+    in_1: String,
+    in_2: String,
+    op: &'static str,
+    out: String,
+}
+
+/// Generates a `bits`-wide ripple-carry adder (`z = x + y`) in the puzzle's format, then swaps
+/// the outputs of `swaps` random pairs of gates. Swaps that would make the circuit cyclic are
+/// rejected, so part 1 stays solvable.
+pub fn generate(rng: &mut Rng, bits: usize, swaps: usize) -> String {
+    let bits = bits.clamp(2, 64);
+    let mut used_names = HashSet::new();
+    let mut wire = |rng: &mut Rng| loop {
+        let name: String = (0..3).map(|_| char::from(b'a' + rng.range(0, 22) as u8)).collect();
+        if used_names.insert(name.clone()) {
+            break name;
+        }
+    };
+
+    let x = |i: usize| format!("x{:02}", i);
+    let y = |i: usize| format!("y{:02}", i);
+    let z = |i: usize| format!("z{:02}", i);
+
+    let mut gates = vec![
+        Gate { in_1: x(0), in_2: y(0), op: "XOR", out: z(0) },
+    ];
+    let mut carry = wire(rng);
+    gates.push(Gate { in_1: x(0), in_2: y(0), op: "AND", out: carry.clone() });
+    for i in 1..bits {
+        let half_sum = wire(rng);
+        let half_carry = wire(rng);
+        let carry_through = wire(rng);
+        let next_carry = if i == bits - 1 { z(bits) } else { wire(rng) };
+        gates.push(Gate { in_1: x(i), in_2: y(i), op: "XOR", out: half_sum.clone() });
+        gates.push(Gate { in_1: x(i), in_2: y(i), op: "AND", out: half_carry.clone() });
+        gates.push(Gate { in_1: half_sum.clone(), in_2: carry.clone(), op: "XOR", out: z(i) });
+        gates.push(Gate { in_1: half_sum, in_2: carry, op: "AND", out: carry_through.clone() });
+        gates.push(Gate { in_1: half_carry, in_2: carry_through, op: "OR", out: next_carry.clone() });
+        carry = next_carry;
+    }
+
+    let mut swapped = HashSet::new();
+    let mut attempts = 0;
+    while swapped.len() < swaps * 2 && attempts < swaps * 100 {
+        attempts += 1;
+        let a = rng.below(gates.len());
+        let b = rng.below(gates.len());
+        if a == b || swapped.contains(&a) || swapped.contains(&b) {
+            continue;
+        }
+        let out_a = gates[a].out.clone();
+        gates[a].out = gates[b].out.clone();
+        gates[b].out = out_a;
+        if is_acyclic(&gates) {
+            swapped.insert(a);
+            swapped.insert(b);
+        } else {
+            let out_a = gates[a].out.clone();
+            gates[a].out = gates[b].out.clone();
+            gates[b].out = out_a;
+        }
+    }
+
+    let mut out = String::new();
+    for i in 0..bits {
+        out += &format!("{}: {}\n", x(i), rng.range(0, 1));
+    }
+    for i in 0..bits {
+        out += &format!("{}: {}\n", y(i), rng.range(0, 1));
+    }
+    out += "\n";
+    rng.shuffle(&mut gates);
+    for gate in &gates {
+        let (in_1, in_2) = if rng.chance(50) { (&gate.in_1, &gate.in_2) } else { (&gate.in_2, &gate.in_1) };
+        out += &format!("{} {} {} -> {}\n", in_1, gate.op, in_2, gate.out);
+    }
+    out
+}
+
+fn is_acyclic(gates: &[Gate]) -> bool {
+    fn visit<'a>(
+        wire: &'a str,
+        by_out: &HashMap<&'a str, &'a Gate>,
+        state: &mut HashMap<&'a str, bool>,
+    ) -> bool {
+        if let Some(&done) = state.get(wire) {
+            return done;
+        }
+        let Some(gate) = by_out.get(wire) else { return true; };
+        state.insert(wire, false);
+        let ok = visit(&gate.in_1, by_out, state) && visit(&gate.in_2, by_out, state);
+        state.insert(wire, ok);
+        ok
+    }
+
+    let by_out: HashMap<&str, &Gate> = gates.iter().map(|g| (g.out.as_str(), g)).collect();
+    let mut state = HashMap::new();
+    gates.iter().all(|g| visit(&g.out, &by_out, &mut state))
+}
@@ -0,0 +1,76 @@
+use std::collections::{HashMap, VecDeque};
+use crate::gen::Rng;
+
+/// Carves a perfect maze (exactly one path between any two cells) over a `cells` x `cells` grid
+/// using an iterative recursive backtracker. Cells sit on odd coordinates of the returned
+/// `(2 * cells + 1)`-square grid, so the border is always wall.
+pub(crate) fn carve(rng: &mut Rng, cells: usize) -> Vec<Vec<u8>> {
+    let cells = cells.max(2);
+    let dim = cells * 2 + 1;
+    let mut grid = vec![vec![b'#'; dim]; dim];
+    let mut visited = vec![vec![false; cells]; cells];
+
+    let mut stack = vec![(0usize, cells - 1)];
+    visited[cells - 1][0] = true;
+    grid[cells * 2 - 1][1] = b'.';
+    while let Some(&(cx, cy)) = stack.last() {
+        let mut unvisited = Vec::with_capacity(4);
+        if cx > 0 && !visited[cy][cx - 1] {
+            unvisited.push((cx - 1, cy));
+        }
+        if cy > 0 && !visited[cy - 1][cx] {
+            unvisited.push((cx, cy - 1));
+        }
+        if cx + 1 < cells && !visited[cy][cx + 1] {
+            unvisited.push((cx + 1, cy));
+        }
+        if cy + 1 < cells && !visited[cy + 1][cx] {
+            unvisited.push((cx, cy + 1));
+        }
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (nx, ny) = *rng.choose(&unvisited);
+        visited[ny][nx] = true;
+        grid[ny * 2 + 1][nx * 2 + 1] = b'.';
+        grid[cy + ny + 1][cx + nx + 1] = b'.';
+        stack.push((nx, ny));
+    }
+    grid
+}
+
+/// Finds the (unique, in a perfect maze) open path between two tiles.
+pub(crate) fn find_path(grid: &[Vec<u8>], start: (usize, usize), end: (usize, usize)) -> Vec<(usize, usize)> {
+    let mut came_from = HashMap::from([(start, start)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some((x, y)) = queue.pop_front() {
+        if (x, y) == end {
+            break;
+        }
+        for (nx, ny) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
+            if grid[ny][nx] != b'#' && !came_from.contains_key(&(nx, ny)) {
+                came_from.insert((nx, ny), (x, y));
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    let mut path = vec![end];
+    let mut cur = end;
+    while cur != start {
+        cur = came_from[&cur];
+        path.push(cur);
+    }
+    path.reverse();
+    path
+}
+
+pub(crate) fn render(grid: &[Vec<u8>]) -> String {
+    let mut out = String::new();
+    for row in grid {
+        out += std::str::from_utf8(row).unwrap();
+        out += "\n";
+    }
+    out
+}
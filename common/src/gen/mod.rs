@@ -0,0 +1,103 @@
+mod maze;
+pub mod day01;
+pub mod day02;
+pub mod day09;
+pub mod day14;
+pub mod day16;
+pub mod day19;
+pub mod day20;
+pub mod day24;
+
+pub const GENERATOR_DAYS: &[u32] = &[1, 2, 9, 14, 16, 19, 20, 24];
+
+/// Days whose generated inputs stay well-formed with arbitrary lines removed (grids don't, since
+/// dropping a row takes part of the outer wall with it), apart from the first
+/// [`header_lines`] lines, which have to stay.
+pub const LINE_SHRINKABLE_DAYS: &[u32] = &[1, 2, 14, 19];
+
+/// Lines at the top of a day's input that describe the rest of it rather than being one more
+/// entry, such as day 19's towel patterns and the blank line after them.
+pub fn header_lines(day: u32) -> usize {
+    match day {
+        19 => 2,
+        _ => 0,
+    }
+}
+
+/// Knobs shared by all generators. `size` is the rough scale of the input (lines, files, maze
+/// cells, adder bits...) and `k` is a day-specific extra (day16: loops, day24: swapped pairs).
+#[derive(Clone, Copy, Debug)]
+pub struct GenParams {
+    pub size: usize,
+    pub seed: u64,
+    pub k: usize,
+}
+
+impl Default for GenParams {
+    fn default() -> Self {
+        Self { size: 16, seed: 0, k: 0 }
+    }
+}
+
+pub fn generate(day: u32, params: &GenParams) -> Option<String> {
+    let mut rng = Rng::new(params.seed);
+    Some(match day {
+        1 => day01::generate(&mut rng, params.size),
+        2 => day02::generate(&mut rng, params.size),
+        9 => day09::generate(&mut rng, params.size),
+        14 => day14::generate(&mut rng, params.size),
+        16 => day16::generate(&mut rng, params.size, params.k),
+        19 => day19::generate(&mut rng, params.size),
+        20 => day20::generate(&mut rng, params.size),
+        24 => day24::generate(&mut rng, params.size, params.k),
+        _ => return None,
+    })
+}
+
+/// Small deterministic PRNG (splitmix64) so generated inputs are reproducible from their seed
+/// without pulling in an RNG crate.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `lo..=hi`.
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo <= hi);
+        let span = (hi - lo) as u64 + 1;
+        lo + (self.next_u64() % span) as i64
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0);
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn chance(&mut self, percent: u32) -> bool {
+        self.next_u64() % 100 < percent as u64
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use sha2::{Digest, Sha256};
+
+// of the SHA-256 digest, which is plenty to tell one input from another
+const HASH_BYTES: usize = 8;
+
+/// Identifies an input by its content, as the trimmed string the days actually see, so that
+/// re-downloading or re-saving it with a different trailing newline doesn't change the hash.
+pub fn input_hash(content: &str) -> String {
+    let mut hasher = InputHasher::new();
+    hasher.update(content.as_bytes());
+    hasher.finish()
+}
+
+/// Incremental version of [`input_hash`] for inputs that are streamed rather than loaded whole.
+#[derive(Clone, Debug)]
+pub struct InputHasher {
+    digest: Sha256,
+    started: bool,
+    // whitespace only counts once something follows it, since the end of the input is trimmed
+    pending_whitespace: Vec<u8>,
+}
+
+impl InputHasher {
+    pub fn new() -> Self {
+        Self { digest: Sha256::new(), started: false, pending_whitespace: Vec::new() }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        let mut run_start = 0;
+        for (i, &byte) in data.iter().enumerate() {
+            if !byte.is_ascii_whitespace() {
+                continue;
+            }
+            // everything since the last whitespace is content, so whatever whitespace was
+            // pending before it counts after all
+            if i > run_start {
+                self.flush_pending();
+                self.digest.update(&data[run_start..i]);
+            }
+            if self.started {
+                self.pending_whitespace.push(byte);
+            }
+            run_start = i + 1;
+        }
+        if data.len() > run_start {
+            self.flush_pending();
+            self.digest.update(&data[run_start..]);
+        }
+    }
+
+    fn flush_pending(&mut self) {
+        self.started = true;
+        self.digest.update(&self.pending_whitespace);
+        self.pending_whitespace.clear();
+    }
+
+    pub fn finish(&self) -> String {
+        self.digest.clone().finalize()[..HASH_BYTES].iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+impl Default for InputHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+/// Compact handle for a string stored in an [`Interner`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Maps names to dense [`Symbol`] ids and back. Ids are assigned in insertion order starting at 0,
+/// so they can also be used to index into plain `Vec`s.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    ids: HashMap<String, Symbol>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(&sym) = self.ids.get(name) {
+            return sym;
+        }
+        let sym = Symbol(u32::try_from(self.names.len()).expect("Too many interned names"));
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), sym);
+        sym
+    }
+
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn resolve(&self, sym: Symbol) -> &str {
+        &self.names[sym.index()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = Symbol> {
+        (0..self.names.len() as u32).map(Symbol)
+    }
+}
+
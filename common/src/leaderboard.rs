@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::time::{Duration, SystemTime};
+use serde::Deserialize;
+use crate::net::{self, NetError};
+
+// the site asks that private leaderboards be polled no more than once every 15 minutes
+const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// Day -> part -> star info, with both keys as strings as in the JSON.
+    pub completion_day_level: HashMap<String, HashMap<String, StarInfo>>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct StarInfo {
+    pub get_star_ts: u64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Unix timestamp at which the member got the given star, if they have it.
+    pub fn star_time(&self, day: u32, part: u8) -> Option<u64> {
+        self.completion_day_level.get(&day.to_string())?
+            .get(&part.to_string())
+            .map(|star| star.get_star_ts)
+    }
+}
+
+/// Returns the leaderboard, only hitting the network if the cached copy is older than 15 minutes.
+/// Only responses that parse are cached, and the old copy is used if fetching a new one fails.
+pub fn get_leaderboard(id: u64) -> Result<Leaderboard, NetError> {
+    let cache_path = net::cache_dir().join(format!("leaderboard_{}.json", id));
+    let cache_age = fs::metadata(&cache_path).and_then(|m| m.modified()).ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+    let cached = || fs::read_to_string(&cache_path).ok().and_then(|json| parse_leaderboard(&json).ok());
+
+    if cache_age.is_some_and(|age| age < CACHE_TTL) {
+        if let Some(board) = cached() {
+            return Ok(board);
+        }
+    }
+
+    let fetched = net::download_private_leaderboard(id)
+        .and_then(|json| parse_leaderboard(&json).map(|board| (board, json)));
+    match fetched {
+        Ok((board, json)) => {
+            if fs::create_dir_all(net::cache_dir()).is_ok() {
+                _ = fs::write(&cache_path, &json);
+            }
+            Ok(board)
+        }
+        Err(e) => match cached() {
+            Some(board) => {
+                eprintln!("Failed to fetch leaderboard ({}), showing the cached copy", e);
+                Ok(board)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+fn parse_leaderboard(json: &str) -> Result<Leaderboard, NetError> {
+    serde_json::from_str(json).map_err(|e| NetError::Transport(format!("Malformed leaderboard JSON: {}", e)))
+}
+
+pub fn format_report(board: &Leaderboard) -> String {
+    let mut members: Vec<&Member> = board.members.values().collect();
+    members.sort_by(|a, b| b.local_score.cmp(&a.local_score).then(a.display_name().cmp(&b.display_name())));
+
+    let mut out = String::new();
+    writeln!(out, "Private leaderboard {} ({} members), event {}", board.owner_id, members.len(), board.event).unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "Star timelines (time after unlock for part 1 / part 2):").unwrap();
+    for member in &members {
+        writeln!(out, "  {} - {} points, {} stars", member.display_name(), member.local_score, member.stars).unwrap();
+        for day in 1..=25 {
+            let Some(p1) = member.star_time(day, 1) else { continue; };
+            let p2 = member.star_time(day, 2)
+                .map(|ts| format_duration(ts.saturating_sub(unlock_secs(day))))
+                .unwrap_or("-".to_string());
+            writeln!(out, "    Day {:>2}: {:>12} / {:>12}", day, format_duration(p1.saturating_sub(unlock_secs(day))), p2).unwrap();
+        }
+    }
+
+    writeln!(out).unwrap();
+    writeln!(out, "Part 1 -> part 2 deltas:").unwrap();
+    for day in 1..=25 {
+        let mut deltas: Vec<(u64, String)> = members.iter()
+            .filter_map(|m| Some((m.star_time(day, 2)?.saturating_sub(m.star_time(day, 1)?), m.display_name())))
+            .collect();
+        if deltas.is_empty() {
+            continue;
+        }
+        deltas.sort();
+        let entries: Vec<String> = deltas.iter()
+            .map(|(delta, name)| format!("{} {}", name, format_duration(*delta)))
+            .collect();
+        writeln!(out, "  Day {:>2}: {}", day, entries.join(", ")).unwrap();
+    }
+    out
+}
+
+fn unlock_secs(day: u32) -> u64 {
+    net::unlock_time(day).expect("leaderboard days run from 1 to 25").duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn format_duration(secs: u64) -> String {
+    let (days, rem) = (secs / 86400, secs % 86400);
+    let hms = format!("{:02}:{:02}:{:02}", rem / 3600, rem % 3600 / 60, rem % 60);
+    if days > 0 {
+        format!("{}d {}", days, hms)
+    } else {
+        hms
+    }
+}
@@ -1 +1,17 @@
+pub mod args;
+pub mod budget;
+pub mod config;
+pub mod crypt;
+pub mod dashboard;
+pub mod describe;
+pub mod diff;
 pub mod file;
+pub mod gen;
+pub mod hash;
+pub mod intern;
+pub mod leaderboard;
+pub mod logging;
+pub mod multi;
+pub mod net;
+pub mod run;
+pub mod scaffold;
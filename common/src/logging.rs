@@ -0,0 +1,74 @@
+use std::env;
+use std::str::FromStr;
+use log::{LevelFilter, Log, Metadata, Record};
+
+struct Logger {
+    default_level: LevelFilter,
+    target_levels: Vec<(String, LevelFilter)>,
+}
+
+impl Logger {
+    fn level_for(&self, target: &str) -> LevelFilter {
+        // most specific matching prefix wins, like env_logger
+        self.target_levels.iter()
+            .filter(|(prefix, _)| target == prefix || target.starts_with(&format!("{}::", prefix)))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default_level)
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_for(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{:<5} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs the logger. Each day logs under its own crate name as the target (e.g. `day06`).
+/// Verbosity comes from `AOC_LOG` or `RUST_LOG` (`trace`, `day06=trace`, `warn,day17=debug`...),
+/// and `-v`/`-vv` on the command line raise the default level to debug/trace.
+pub fn init() {
+    let spec = env::var("AOC_LOG").or_else(|_| env::var("RUST_LOG")).unwrap_or_default();
+    let mut logger = Logger { default_level: LevelFilter::Warn, target_levels: Vec::new() };
+    for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+        match directive.split_once('=') {
+            Some((target, level)) => {
+                if let Ok(level) = LevelFilter::from_str(level) {
+                    logger.target_levels.push((target.to_string(), level));
+                }
+            }
+            None => {
+                if let Ok(level) = LevelFilter::from_str(directive) {
+                    logger.default_level = level;
+                }
+            }
+        }
+    }
+
+    let verbosity = env::args().skip(1)
+        .filter(|arg| arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v'))
+        .map(|arg| arg.len() - 1)
+        .sum::<usize>();
+    match verbosity {
+        0 => {}
+        1 => logger.default_level = logger.default_level.max(LevelFilter::Debug),
+        _ => logger.default_level = LevelFilter::Trace,
+    }
+
+    let max_level = logger.target_levels.iter()
+        .map(|(_, level)| *level)
+        .chain([logger.default_level])
+        .max()
+        .unwrap();
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{exit, Command};
+use crate::file::parse_answers;
+
+const INPUT_SUFFIXES: &[&str] = &[".txt.enc", ".txt"];
+
+#[derive(Clone, Debug)]
+enum Verdict {
+    Pass(String),
+    Fail { got: String, expected: String },
+    Unchecked(String),
+    Missing,
+}
+
+/// Runs the current day binary once per input in `dir` (`NAME.txt` or `NAME.txt.enc`, with
+/// optional expected answers in `NAME.answers`), prints a pass/fail matrix and exits.
+pub fn run_all_inputs(day: u32, dir: &Path) -> ! {
+    let inputs = find_inputs(dir);
+    if inputs.is_empty() {
+        eprintln!("No inputs found in {}", dir.display());
+        exit(1);
+    }
+
+    let exe = env::current_exe().expect("Failed to locate current executable");
+    let passthrough_args = get_passthrough_args();
+
+    let mut rows = Vec::new();
+    for (name, path) in &inputs {
+        let output = Command::new(&exe)
+            .args(&passthrough_args)
+            .arg("--input")
+            .arg(path)
+            .output();
+        let expected = fs::read_to_string(dir.join(format!("{}.answers", name)))
+            .map(|content| parse_answers(&content))
+            .unwrap_or_default();
+
+        let row = match output {
+            Ok(output) if output.status.success() => {
+                let actual = parse_answers(&String::from_utf8_lossy(&output.stdout));
+                Ok(judge(&actual, &expected))
+            }
+            Ok(output) => Err(format!("ERROR ({})", output.status)),
+            Err(e) => Err(format!("ERROR ({})", e)),
+        };
+        rows.push((name.clone(), row));
+    }
+
+    let parts: Vec<u8> = rows.iter()
+        .filter_map(|(_, row)| row.as_ref().ok())
+        .flat_map(|verdicts| verdicts.keys().copied())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let name_width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max("Input".len());
+
+    println!("Day {}: {} inputs from {}", day, rows.len(), dir.display());
+    let mut header = format!("{:<width$}", "Input", width = name_width);
+    for part in &parts {
+        header += &format!("  {:<30}", format!("Part {}", part));
+    }
+    println!("{}", header.trim_end());
+
+    let mut any_failed = false;
+    for (name, row) in &rows {
+        let mut line = format!("{:<width$}", name, width = name_width);
+        match row {
+            Ok(verdicts) => {
+                for part in &parts {
+                    let cell = match verdicts.get(part).unwrap_or(&Verdict::Missing) {
+                        Verdict::Pass(answer) => format!("PASS {}", answer),
+                        Verdict::Fail { got, expected } => {
+                            any_failed = true;
+                            format!("FAIL {} (expected {})", got, expected)
+                        }
+                        Verdict::Unchecked(answer) => format!("? {}", answer),
+                        Verdict::Missing => {
+                            any_failed = true;
+                            "MISSING".to_string()
+                        }
+                    };
+                    line += &format!("  {:<30}", cell);
+                }
+            }
+            Err(msg) => {
+                any_failed = true;
+                line += &format!("  {}", msg);
+            }
+        }
+        println!("{}", line.trim_end());
+    }
+
+    exit(if any_failed { 1 } else { 0 });
+}
+
+fn judge(actual: &BTreeMap<u8, String>, expected: &BTreeMap<u8, String>) -> BTreeMap<u8, Verdict> {
+    let mut verdicts = BTreeMap::new();
+    for part in actual.keys().chain(expected.keys()) {
+        let verdict = match (actual.get(part), expected.get(part)) {
+            (Some(got), Some(exp)) if got == exp => Verdict::Pass(got.clone()),
+            (Some(got), Some(exp)) => Verdict::Fail { got: got.clone(), expected: exp.clone() },
+            (Some(got), None) => Verdict::Unchecked(got.clone()),
+            (None, _) => Verdict::Missing,
+        };
+        verdicts.insert(*part, verdict);
+    }
+    verdicts
+}
+
+fn find_inputs(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        eprintln!("Could not read input directory {}", dir.display());
+        exit(1);
+    };
+    let mut inputs: BTreeMap<String, PathBuf> = BTreeMap::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(name) = INPUT_SUFFIXES.iter().find_map(|suffix| file_name.strip_suffix(suffix)) else {
+            continue;
+        };
+        // prefer the plaintext copy when both exist
+        if !inputs.contains_key(name) || file_name.ends_with(".txt") {
+            inputs.insert(name.to_string(), entry.path());
+        }
+    }
+    inputs.into_iter().collect()
+}
+
+// everything except the --inputs flag itself, so that --set, --example etc. still apply
+fn get_passthrough_args() -> Vec<String> {
+    let mut args = Vec::new();
+    let mut it = env::args().skip(1);
+    while let Some(arg) = it.next() {
+        if arg == "--inputs" {
+            it.next();
+        } else {
+            args.push(arg);
+        }
+    }
+    args
+}
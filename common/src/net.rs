@@ -0,0 +1,229 @@
+use std::{env, fs, thread};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::StatusCode;
+use crate::{args, config};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_USER_AGENT: &str = "github.com/caseif/AdventOfCode-2024 (aoc2024-common)";
+const DEFAULT_MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+// puzzles unlock at midnight US-Eastern (UTC-5 in December), e.g. 2024-12-01T05:00:00Z for day 1
+const UNLOCK_HOUR_UTC: u64 = 5;
+const LAST_DAY: u32 = 25;
+
+static REQUEST_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NetError {
+    MissingToken,
+    InvalidDay(u32),
+    Locked(Duration),
+    Unauthorized,
+    NotFound,
+    RateLimited,
+    Status(u16),
+    Transport(String),
+}
+
+impl Display for NetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetError::MissingToken => write!(f, "AOC_TOKEN is not set"),
+            NetError::InvalidDay(day) => write!(f, "There is no day {} (days go from 1 to {})", day, LAST_DAY),
+            NetError::Locked(remaining) => write!(
+                f,
+                "Puzzle unlocks in {}s (pass --wait-for-unlock or set AOC_WAIT_FOR_UNLOCK to wait)",
+                remaining.as_secs(),
+            ),
+            NetError::Unauthorized => write!(f, "Session token was rejected"),
+            NetError::NotFound => write!(f, "Not found (puzzle may not be unlocked yet)"),
+            NetError::RateLimited => write!(f, "Rate limited by server"),
+            NetError::Status(code) => write!(f, "Unexpected HTTP status {}", code),
+            NetError::Transport(msg) => write!(f, "Request failed: {}", msg),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SubmitOutcome {
+    Correct,
+    Incorrect,
+    TooSoon,
+    WrongLevel,
+    Unknown(String),
+}
+
+/// Base URL of the site, overridable with `AOC_BASE_URL` so that everything can be pointed at a
+/// local mock server.
+pub fn base_url() -> String {
+    env::var("AOC_BASE_URL").map(|s| s.trim_end_matches('/').to_string())
+        .unwrap_or(DEFAULT_BASE_URL.to_string())
+}
+
+pub fn unlock_time(day: u32) -> Result<SystemTime, NetError> {
+    if !(1..=LAST_DAY).contains(&day) {
+        return Err(NetError::InvalidDay(day));
+    }
+    let day_1_secs = days_since_epoch_to_dec_1(config::year()) * 86400 + UNLOCK_HOUR_UTC * 3600;
+    Ok(UNIX_EPOCH + Duration::from_secs(day_1_secs + (day as u64 - 1) * 86400))
+}
+
+// days from 1970-01-01 to December 1st of the given year
+fn days_since_epoch_to_dec_1(year: u32) -> u64 {
+    let is_leap = |y: u32| (y.is_multiple_of(4) && !y.is_multiple_of(100)) || y.is_multiple_of(400);
+    let whole_years: u64 = (1970..year).map(|y| if is_leap(y) { 366 } else { 365 }).sum();
+    // Jan through Nov
+    let before_dec = 334 + if is_leap(year) { 1 } else { 0 };
+    whole_years + before_dec
+}
+
+/// Returns `Ok` once the given day's puzzle is available, either immediately or after sleeping
+/// until it unlocks if waiting was requested.
+pub fn ensure_unlocked(day: u32) -> Result<(), NetError> {
+    let Ok(remaining) = unlock_time(day)?.duration_since(SystemTime::now()) else {
+        return Ok(());
+    };
+    if !(args::flag("--wait-for-unlock") || env::var("AOC_WAIT_FOR_UNLOCK").is_ok()) {
+        return Err(NetError::Locked(remaining));
+    }
+    eprintln!("Day {} unlocks in {}s, waiting...", day, remaining.as_secs());
+    // a little slack so we don't beat the server's clock
+    thread::sleep(remaining + Duration::from_secs(1));
+    Ok(())
+}
+
+/// Directory for state shared between runs (request timestamps, cached responses), overridable
+/// with `AOC_CACHE_DIR`.
+pub fn cache_dir() -> PathBuf {
+    env::var("AOC_CACHE_DIR").map(PathBuf::from)
+        .unwrap_or_else(|_| env::temp_dir().join("aoc2024"))
+}
+
+pub fn download_input(day: u32) -> Result<String, NetError> {
+    ensure_unlocked(day)?;
+    let res = send(
+        client()?.get(format!("{}/{}/day/{}/input", base_url(), config::year(), day))
+    )?;
+    read_body(check_status(res)?)
+}
+
+pub fn download_puzzle_page(day: u32) -> Result<String, NetError> {
+    ensure_unlocked(day)?;
+    let res = send(
+        client()?.get(format!("{}/{}/day/{}", base_url(), config::year(), day))
+    )?;
+    read_body(check_status(res)?)
+}
+
+pub fn download_private_leaderboard(id: u64) -> Result<String, NetError> {
+    let res = send(
+        client()?.get(format!("{}/{}/leaderboard/private/view/{}.json", base_url(), config::year(), id))
+    )?;
+    read_body(check_status(res)?)
+}
+
+pub fn submit_answer(day: u32, part: u8, answer: &str) -> Result<SubmitOutcome, NetError> {
+    ensure_unlocked(day)?;
+    let res = send(
+        client()?.post(format!("{}/{}/day/{}/answer", base_url(), config::year(), day))
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(format!("level={}&answer={}", part, url_encode(answer)))
+    )?;
+    let body = read_body(check_status(res)?)?;
+
+    Ok(if body.contains("That's the right answer") {
+        SubmitOutcome::Correct
+    } else if body.contains("That's not the right answer") {
+        SubmitOutcome::Incorrect
+    } else if body.contains("You gave an answer too recently") {
+        SubmitOutcome::TooSoon
+    } else if body.contains("You don't seem to be solving the right level") {
+        SubmitOutcome::WrongLevel
+    } else {
+        SubmitOutcome::Unknown(body)
+    })
+}
+
+fn get_token() -> Result<String, NetError> {
+    env::var("AOC_TOKEN").map_err(|_| NetError::MissingToken)
+}
+
+pub(crate) fn client() -> Result<ClientWithToken, NetError> {
+    let token = get_token()?;
+    let user_agent = env::var("AOC_USER_AGENT").unwrap_or(DEFAULT_USER_AGENT.to_string());
+    let client = Client::builder()
+        .user_agent(user_agent)
+        .build()
+        .map_err(|e| NetError::Transport(e.to_string()))?;
+    Ok(ClientWithToken { client, token })
+}
+
+pub(crate) struct ClientWithToken {
+    client: Client,
+    token: String,
+}
+
+impl ClientWithToken {
+    pub(crate) fn get(&self, url: String) -> RequestBuilder {
+        self.client.get(url).header("Cookie", format!("session={}", self.token))
+    }
+
+    pub(crate) fn post(&self, url: String) -> RequestBuilder {
+        self.client.post(url).header("Cookie", format!("session={}", self.token))
+    }
+}
+
+/// Sends a request, first waiting out the minimum interval since the last request made by any
+/// process sharing the same cache directory.
+pub(crate) fn send(req: RequestBuilder) -> Result<Response, NetError> {
+    let _guard = REQUEST_LOCK.lock().unwrap();
+    let min_interval = env::var("AOC_MIN_REQUEST_INTERVAL_MS").ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_MIN_REQUEST_INTERVAL);
+
+    let stamp_path = cache_dir().join("last_request");
+    let last_request = fs::read_to_string(&stamp_path).ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+    if let Some(elapsed) = last_request.and_then(|t| SystemTime::now().duration_since(t).ok()) {
+        if elapsed < min_interval {
+            thread::sleep(min_interval - elapsed);
+        }
+    }
+
+    let now_millis = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+    if fs::create_dir_all(cache_dir()).is_ok() {
+        _ = fs::write(&stamp_path, now_millis.to_string());
+    }
+
+    req.send().map_err(|e| NetError::Transport(e.to_string()))
+}
+
+fn check_status(res: Response) -> Result<Response, NetError> {
+    match res.status() {
+        s if s.is_success() => Ok(res),
+        // the site answers a missing or bad session with 400 rather than 401
+        StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED => Err(NetError::Unauthorized),
+        StatusCode::NOT_FOUND => Err(NetError::NotFound),
+        StatusCode::TOO_MANY_REQUESTS => Err(NetError::RateLimited),
+        s => Err(NetError::Status(s.as_u16())),
+    }
+}
+
+fn read_body(res: Response) -> Result<String, NetError> {
+    res.text().map_err(|e| NetError::Transport(e.to_string()))
+}
+
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use crate::budget::{Budget, TIMED_OUT_EXIT_CODE};
+use crate::file::{parse_answers, INPUT_HASH_PREFIX};
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+// how long past its deadline a day gets to stop on its own before we kill it
+const KILL_GRACE: Duration = Duration::from_secs(2);
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    Ok,
+    TimedOut(String),
+    Failed(String),
+    NotBuilt,
+}
+
+#[derive(Clone, Debug)]
+pub struct DayRun {
+    pub day: u32,
+    pub status: Status,
+    /// Whatever answers the day printed, even if it didn't finish.
+    pub answers: BTreeMap<u8, String>,
+    /// Time taken by each part, measured from when the previous answer (or nothing, for the first
+    /// part) was printed, so part 1's time includes parsing the input.
+    pub part_times: BTreeMap<u8, Duration>,
+    pub elapsed: Duration,
+    /// Hash of the input the day loaded, if it got that far.
+    pub input_hash: Option<String>,
+}
+
+/// Path of the day's binary, built alongside the currently running one.
+pub fn day_binary(day: u32) -> Option<PathBuf> {
+    let exe = env::current_exe().ok()?;
+    let path = exe.parent()?.join(format!("day{:02}{}", day, env::consts::EXE_SUFFIX));
+    path.exists().then_some(path)
+}
+
+/// The wall-clock budget for `day`: `--timeout`, else `timeout` in aoc.toml, else the default.
+pub fn timeout_for_day(day: u32) -> Duration {
+    Budget::for_day(day).limit().unwrap_or(DEFAULT_TIMEOUT)
+}
+
+/// Runs a day's binary from the current directory. The day is told its budget through
+/// `--timeout` so it can give up cleanly, and is killed if it hasn't exited shortly after.
+pub fn run_day(day: u32, timeout: Duration) -> DayRun {
+    let mut run = DayRun {
+        day,
+        status: Status::NotBuilt,
+        answers: BTreeMap::new(),
+        part_times: BTreeMap::new(),
+        elapsed: Duration::ZERO,
+        input_hash: None,
+    };
+    let Some(exe) = day_binary(day) else {
+        return run;
+    };
+
+    let start = Instant::now();
+    let spawned = Command::new(&exe)
+        .arg("--timeout")
+        .arg(timeout.as_secs_f64().to_string())
+        .env("AOC_REPORT_INPUT_HASH", "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            run.status = Status::Failed(format!("failed to start {}: {}", exe.display(), e));
+            return run;
+        }
+    };
+    let stdout = read_lines_in_background(child.stdout.take(), start);
+    let stderr = read_in_background(child.stderr.take());
+
+    let (exit_status, killed) = wait_with_deadline(&mut child, start + timeout + KILL_GRACE);
+    run.elapsed = start.elapsed();
+    let stdout_lines = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    let mut last_answer_at = Duration::ZERO;
+    for (at, line) in &stdout_lines {
+        for (part, answer) in parse_answers(line) {
+            run.part_times.insert(part, at.saturating_sub(last_answer_at));
+            run.answers.insert(part, answer);
+            last_answer_at = *at;
+        }
+    }
+
+    run.input_hash = stderr.lines()
+        .find_map(|line| line.strip_prefix(INPUT_HASH_PREFIX))
+        .map(|hash| hash.trim().to_string());
+
+    let last_line = stderr.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("").trim().to_string();
+    run.status = if killed {
+        Status::TimedOut(format!("killed after {:.1}s", run.elapsed.as_secs_f64()))
+    } else {
+        match exit_status.and_then(|status| status.code()) {
+            Some(0) => Status::Ok,
+            Some(TIMED_OUT_EXIT_CODE) => Status::TimedOut(last_line),
+            Some(code) => Status::Failed(panic_message(&stderr).unwrap_or(format!("exit code {}", code))),
+            None => Status::Failed("terminated by signal".to_string()),
+        }
+    };
+    run
+}
+
+pub fn format_header() -> String {
+    format!("{:<4} {:<10} {:>8}  {:<20}  {:<20}  Notes", "Day", "Status", "Time", "Part 1", "Part 2")
+}
+
+pub fn format_row(run: &DayRun) -> String {
+    let (status, notes) = match &run.status {
+        Status::Ok => ("OK", ""),
+        Status::TimedOut(msg) => ("TIMED OUT", msg.as_str()),
+        Status::Failed(msg) => ("FAILED", msg.as_str()),
+        Status::NotBuilt => ("NOT BUILT", "run `cargo build --release` first"),
+    };
+    let time = if run.status == Status::NotBuilt {
+        "-".to_string()
+    } else {
+        format!("{:.2}s", run.elapsed.as_secs_f64())
+    };
+    let answer = |part: u8| run.answers.get(&part).map(String::as_str).unwrap_or("-").to_string();
+    format!("{:<4} {:<10} {:>8}  {:<20}  {:<20}  {}", run.day, status, time, answer(1), answer(2), notes)
+        .trim_end()
+        .to_string()
+}
+
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            _ = pipe.read_to_end(&mut buf);
+        }
+        String::from_utf8_lossy(&buf).to_string()
+    })
+}
+
+// stdout is line buffered, so each line's arrival time is close to when it was printed
+fn read_lines_in_background<R: Read + Send + 'static>(pipe: Option<R>, start: Instant) -> JoinHandle<Vec<(Duration, String)>> {
+    thread::spawn(move || {
+        let Some(pipe) = pipe else {
+            return Vec::new();
+        };
+        BufReader::new(pipe).lines()
+            .map_while(Result::ok)
+            .map(|line| (start.elapsed(), line))
+            .collect()
+    })
+}
+
+fn wait_with_deadline(child: &mut Child, deadline: Instant) -> (Option<ExitStatus>, bool) {
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return (Some(status), false),
+            Ok(None) if Instant::now() >= deadline => {
+                _ = child.kill();
+                _ = child.wait();
+                return (None, true);
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(_) => return (None, false),
+        }
+    }
+}
+
+// the line following "thread 'main' panicked at ..." is the panic message itself
+fn panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr.lines().skip_while(|line| !line.contains("panicked at"));
+    lines.next()?;
+    lines.next().map(|line| line.trim().to_string())
+}
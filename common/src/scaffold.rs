@@ -0,0 +1,59 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DAY_CARGO_TEMPLATE: &str = include_str!("../templates/day/Cargo.toml.tmpl");
+const DAY_MAIN_TEMPLATE: &str = include_str!("../templates/day/main.rs.tmpl");
+const YEAR_CARGO_TEMPLATE: &str = include_str!("../templates/year/Cargo.toml.tmpl");
+const YEAR_CONFIG_TEMPLATE: &str = include_str!("../templates/year/aoc.toml.tmpl");
+const YEAR_GITIGNORE_TEMPLATE: &str = include_str!("../templates/year/gitignore.tmpl");
+
+/// Creates `dayNN/` under the workspace root with a Cargo manifest and a `main` that reads its
+/// input through the common crate. The workspace's `day*` glob picks it up without further edits.
+pub fn new_day(root: &Path, day: u32) -> Result<Vec<PathBuf>, String> {
+    if !root.join("Cargo.toml").exists() {
+        return Err(format!("No Cargo.toml in {} (run this from the workspace root)", root.display()));
+    }
+    let day_dir = root.join(format!("day{:02}", day));
+    if day_dir.exists() {
+        return Err(format!("{} already exists", day_dir.display()));
+    }
+
+    let vars = [("DAY", day.to_string()), ("DAY_PADDED", format!("{:02}", day))];
+    let main_name = format!("day{:02}.rs", day);
+    write_files(&day_dir, &[
+        ("Cargo.toml", render(DAY_CARGO_TEMPLATE, &vars)),
+        (main_name.as_str(), render(DAY_MAIN_TEMPLATE, &vars)),
+    ])
+}
+
+/// Creates a fresh workspace for another year's event in `dir`, depending on this copy of the
+/// common crate. Days are then added to it with [`new_day`].
+pub fn new_year(dir: &Path, year: u32) -> Result<Vec<PathBuf>, String> {
+    if fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_some()) {
+        return Err(format!("{} already exists and is not empty", dir.display()));
+    }
+
+    let common_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let common_path = common_path.canonicalize().unwrap_or(common_path);
+    let vars = [("YEAR", year.to_string()), ("COMMON_PATH", common_path.display().to_string())];
+    write_files(dir, &[
+        ("Cargo.toml", render(YEAR_CARGO_TEMPLATE, &vars)),
+        ("aoc.toml", render(YEAR_CONFIG_TEMPLATE, &vars)),
+        (".gitignore", render(YEAR_GITIGNORE_TEMPLATE, &vars)),
+    ])
+}
+
+fn render(template: &str, vars: &[(&str, String)]) -> String {
+    vars.iter().fold(template.to_string(), |acc, (key, value)| acc.replace(&format!("{{{{{}}}}}", key), value))
+}
+
+fn write_files(dir: &Path, files: &[(&str, String)]) -> Result<Vec<PathBuf>, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let mut written = Vec::new();
+    for (name, content) in files {
+        let path = dir.join(name);
+        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
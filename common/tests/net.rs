@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::env;
+use std::sync::Mutex;
+use std::time::Duration;
+use aoc2024_common::net::{download_input, submit_answer, NetError, SubmitOutcome};
+use aoc2024_mock_server::{MockConfig, MockServer};
+
+const SESSION: &str = "test-session";
+const INPUT: &str = "3   4\n4   3\n";
+
+// the client is configured through the environment, so tests can't run alongside each other
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+fn config() -> MockConfig {
+    MockConfig {
+        session: SESSION.to_string(),
+        inputs: HashMap::from([(1, INPUT.to_string())]),
+        answers: HashMap::from([((1, 1), "11".to_string()), ((1, 2), "31".to_string())]),
+        unlocked_through: 1,
+        ..MockConfig::default()
+    }
+}
+
+/// Runs `f` against a fresh mock server, with the client pointed at it using `token`.
+fn with_server<T>(config: MockConfig, token: &str, f: impl FnOnce(&MockServer) -> T) -> T {
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let server = MockServer::start("127.0.0.1:0", config).expect("Failed to start mock server");
+    let cache_dir = env::temp_dir().join(format!("aoc2024-net-test-{}", std::process::id()));
+    env::set_var("AOC_BASE_URL", server.base_url());
+    env::set_var("AOC_TOKEN", token);
+    env::set_var("AOC_CACHE_DIR", &cache_dir);
+    env::set_var("AOC_MIN_REQUEST_INTERVAL_MS", "0");
+    f(&server)
+}
+
+#[test]
+fn downloads_input() {
+    assert_eq!(with_server(config(), SESSION, |_| download_input(1)), Ok(INPUT.to_string()));
+}
+
+#[test]
+fn rejects_bad_token() {
+    assert_eq!(with_server(config(), "wrong-session", |_| download_input(1)), Err(NetError::Unauthorized));
+}
+
+#[test]
+fn locked_day_is_not_found() {
+    assert_eq!(with_server(config(), SESSION, |_| download_input(2)), Err(NetError::NotFound));
+}
+
+#[test]
+fn invalid_day() {
+    assert_eq!(with_server(config(), SESSION, |_| download_input(0)), Err(NetError::InvalidDay(0)));
+    assert_eq!(with_server(config(), SESSION, |_| submit_answer(26, 1, "11")), Err(NetError::InvalidDay(26)));
+}
+
+#[test]
+fn rate_limited() {
+    let config = MockConfig { min_request_interval: Duration::from_secs(3600), ..config() };
+    let (first, second) = with_server(config, SESSION, |_| (download_input(1), download_input(1)));
+    assert_eq!(first, Ok(INPUT.to_string()));
+    assert_eq!(second, Err(NetError::RateLimited));
+}
+
+#[test]
+fn submission_outcomes() {
+    with_server(config(), SESSION, |server| {
+        assert_eq!(submit_answer(1, 2, "31"), Ok(SubmitOutcome::WrongLevel));
+        assert_eq!(submit_answer(1, 1, "11"), Ok(SubmitOutcome::Correct));
+        assert_eq!(submit_answer(1, 2, "30"), Ok(SubmitOutcome::Incorrect));
+        assert_eq!(submit_answer(1, 2, "31"), Ok(SubmitOutcome::TooSoon));
+        assert_eq!(submit_answer(1, 1, "11"), Ok(SubmitOutcome::TooSoon));
+
+        let expected: Vec<(u32, u8, String)> = [(1, 2, "31"), (1, 1, "11"), (1, 2, "30"), (1, 2, "31"), (1, 1, "11")]
+            .into_iter()
+            .map(|(day, part, answer)| (day, part, answer.to_string()))
+            .collect();
+        assert_eq!(server.submissions(), expected);
+    });
+}
+
+#[test]
+fn wrong_level_after_solving() {
+    with_server(config(), SESSION, |_| {
+        assert_eq!(submit_answer(1, 1, "11"), Ok(SubmitOutcome::Correct));
+        assert_eq!(submit_answer(1, 1, "11"), Ok(SubmitOutcome::WrongLevel));
+        assert_eq!(submit_answer(1, 2, "31"), Ok(SubmitOutcome::Correct));
+    });
+}
@@ -1,37 +1,204 @@
+use std::collections::HashMap;
+use std::io::BufRead;
 use itertools::Itertools;
-use aoc2024_common::file::read_input_lines;
+use aoc2024_common::args;
+use aoc2024_common::file::read_input_stream;
 
 const DAY: u32 = 1;
 
+const DEFAULT_REPORT_TOP: usize = 10;
+const HISTOGRAM_BAR_WIDTH: usize = 40;
+
 fn main() {
-    let (list_a, list_b) = parse_lists();
+    let (list_a, list_b) = parse_lists(read_input_stream(DAY));
+    if args::flag("--report") {
+        let report = build_report(&list_a, &list_b, args::value_or("--top", DEFAULT_REPORT_TOP));
+        match args::value("--format").as_deref() {
+            None | Some("text") => print!("{}", format_report_text(&report)),
+            Some("csv") => print!("{}", format_report_csv(&report)),
+            Some(other) => panic!("Unknown report format '{}' (expected text or csv)", other),
+        }
+        return;
+    }
+
     println!("Part 1: {}", solve_p1(&list_a, &list_b));
     println!("Part 2: {}", solve_p2(&list_a, &list_b));
 }
 
-fn parse_lists() -> (Vec<i32>, Vec<i32>) {
-    let lines = read_input_lines(DAY);
-    let (list_a_unsorted, list_b_unsorted): (Vec<_>, Vec<_>) = lines.iter()
-        .map(|line| line.split_whitespace().collect_tuple().unwrap())
-        .map(|(a, b)| (a.parse::<i32>().unwrap(), b.parse::<i32>().unwrap()))
-        .unzip();
-    (list_a_unsorted.into_iter().sorted().collect(), list_b_unsorted.into_iter().sorted().collect())
+// reads a line at a time into the same buffer so that large exports never have to be held in
+// memory as text, only as the two lists of IDs
+fn parse_lists(mut reader: impl BufRead) -> (Vec<i64>, Vec<i64>) {
+    let mut list_a = Vec::new();
+    let mut list_b = Vec::new();
+    let mut line = String::new();
+    let mut line_number = 0;
+    while reader.read_line(&mut line).expect("Failed to read input") > 0 {
+        line_number += 1;
+        if !line.trim().is_empty() {
+            let Some((a, b)) = line.split_whitespace().collect_tuple() else {
+                panic!("line {}: expected two numbers", line_number);
+            };
+            list_a.push(a.parse::<i64>().unwrap());
+            list_b.push(b.parse::<i64>().unwrap());
+        }
+        line.clear();
+    }
+    list_a.sort_unstable();
+    list_b.sort_unstable();
+    (list_a, list_b)
 }
 
-fn solve_p1(list_a: &Vec<i32>, list_b: &Vec<i32>) -> i32 {
-    let mut sum = 0;
-    for i in 0..list_a.len() {
-        sum += i32::abs(list_a[i] - list_b[i]);
+fn solve_p1(list_a: &[i64], list_b: &[i64]) -> i64 {
+    list_a.iter().zip(list_b).map(|(a, b)| (a - b).abs()).sum()
+}
+
+fn solve_p2(list_a: &[i64], list_b: &[i64]) -> i64 {
+    let mut counts: HashMap<i64, i64> = HashMap::new();
+    for &b in list_b {
+        *counts.entry(b).or_default() += 1;
     }
 
-    sum
+    list_a.iter().map(|a| a * counts.get(a).copied().unwrap_or(0)).sum()
+}
+
+// IDs along with how many times they appear
+type IdCounts = Vec<(i64, usize)>;
+
+#[derive(Clone, Copy, Debug)]
+struct Pair {
+    /// Position of the pair in the sorted lists, starting at 1.
+    rank: usize,
+    left: i64,
+    right: i64,
+    distance: i64,
 }
 
-fn solve_p2(list_a: &Vec<i32>, list_b: &Vec<i32>) -> i32 {
-    let mut score = 0;
-    for i in 0..list_a.len() {
-        score += list_a[i] * (list_b.iter().filter(|&b| *b == list_a[i]).count() as i32);
+struct Report {
+    pairs: usize,
+    total_distance: i64,
+    /// IDs with no match in the other list.
+    only_left: IdCounts,
+    only_right: IdCounts,
+    largest: Vec<Pair>,
+    /// Pair counts per order of magnitude of distance: 0, 1-9, 10-99, ...
+    histogram: Vec<usize>,
+}
+
+fn build_report(list_a: &[i64], list_b: &[i64], top: usize) -> Report {
+    let (only_left, only_right) = find_unmatched(list_a, list_b);
+
+    let pairs: Vec<Pair> = list_a.iter().zip(list_b)
+        .enumerate()
+        .map(|(i, (&left, &right))| Pair { rank: i + 1, left, right, distance: (left - right).abs() })
+        .collect();
+    let largest = pairs.iter()
+        .copied()
+        .sorted_by_key(|pair| (-pair.distance, pair.rank))
+        .take(top)
+        .collect();
+
+    let mut histogram = Vec::new();
+    for pair in &pairs {
+        let bucket = if pair.distance == 0 { 0 } else { pair.distance.ilog10() as usize + 1 };
+        if histogram.len() <= bucket {
+            histogram.resize(bucket + 1, 0);
+        }
+        histogram[bucket] += 1;
     }
 
-    score
+    Report {
+        pairs: pairs.len(),
+        total_distance: solve_p1(list_a, list_b),
+        only_left,
+        only_right,
+        largest,
+        histogram,
+    }
+}
+
+// walks both sorted lists together, so this is linear rather than a lookup per ID
+fn find_unmatched(list_a: &[i64], list_b: &[i64]) -> (IdCounts, IdCounts) {
+    let runs = |list: &[i64]| list.iter().dedup_with_count().map(|(n, &id)| (id, n)).collect::<Vec<_>>();
+    let (runs_a, runs_b) = (runs(list_a), runs(list_b));
+
+    let mut only_left = Vec::new();
+    let mut only_right = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < runs_a.len() || j < runs_b.len() {
+        match (runs_a.get(i), runs_b.get(j)) {
+            (Some(a), Some(b)) if a.0 == b.0 => {
+                i += 1;
+                j += 1;
+            }
+            (Some(a), Some(b)) if a.0 < b.0 => {
+                only_left.push(*a);
+                i += 1;
+            }
+            (Some(a), None) => {
+                only_left.push(*a);
+                i += 1;
+            }
+            (_, Some(b)) => {
+                only_right.push(*b);
+                j += 1;
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+    (only_left, only_right)
+}
+
+fn histogram_label(bucket: usize) -> String {
+    match bucket {
+        0 => "0".to_string(),
+        1 => "1-9".to_string(),
+        _ => format!("{}-{}", 10i64.pow(bucket as u32 - 1), 10i64.pow(bucket as u32) - 1),
+    }
+}
+
+fn format_report_text(report: &Report) -> String {
+    let mut out = format!("Pairs: {}\nTotal distance: {}\n", report.pairs, report.total_distance);
+
+    for (label, ids) in [("left", &report.only_left), ("right", &report.only_right)] {
+        out += &format!("\nOnly in {} list ({} IDs):\n", label, ids.len());
+        for &(id, count) in ids {
+            if count > 1 {
+                out += &format!("  {} (x{})\n", id, count);
+            } else {
+                out += &format!("  {}\n", id);
+            }
+        }
+    }
+
+    out += &format!("\nLargest distances (top {}):\n", report.largest.len());
+    for pair in &report.largest {
+        out += &format!("  #{:<8} {:>10} {:>10}   distance {}\n", pair.rank, pair.left, pair.right, pair.distance);
+    }
+
+    out += "\nDistance histogram:\n";
+    let max_count = report.histogram.iter().copied().max().unwrap_or(0).max(1);
+    let label_width = (0..report.histogram.len()).map(|b| histogram_label(b).len()).max().unwrap_or(0);
+    for (bucket, &count) in report.histogram.iter().enumerate() {
+        let bar = "#".repeat((count * HISTOGRAM_BAR_WIDTH).div_ceil(max_count));
+        out += &format!("  {:>width$} | {:>8} {}\n", histogram_label(bucket), count, bar, width = label_width);
+    }
+    out
+}
+
+fn format_report_csv(report: &Report) -> String {
+    let mut out = "section,rank,left,right,distance,count\n".to_string();
+    out += &format!("total,,,,{},{}\n", report.total_distance, report.pairs);
+    for &(id, count) in &report.only_left {
+        out += &format!("only_left,,{},,,{}\n", id, count);
+    }
+    for &(id, count) in &report.only_right {
+        out += &format!("only_right,,,{},,{}\n", id, count);
+    }
+    for pair in &report.largest {
+        out += &format!("largest,{},{},{},{},\n", pair.rank, pair.left, pair.right, pair.distance);
+    }
+    for (bucket, &count) in report.histogram.iter().enumerate() {
+        out += &format!("histogram,,,,{},{}\n", histogram_label(bucket), count);
+    }
+    out
 }
@@ -1,52 +1,215 @@
+use std::fmt::{Display, Formatter};
+use itertools::Itertools;
+use aoc2024_common::{args, config};
 use aoc2024_common::file::read_input_lines;
 
 const DAY: u32 = 2;
 
+const DEFAULT_MIN_DELTA: i32 = 1;
+const DEFAULT_MAX_DELTA: i32 = 3;
+const DEFAULT_MAX_REMOVALS: usize = 1;
+
 fn main() {
-    println!("Part 1: {}", solve_p1());
-    println!("Part 2: {}", solve_p2());
+    let rules = Rules {
+        min_delta: config::get(DAY, "min_delta", DEFAULT_MIN_DELTA),
+        max_delta: config::get(DAY, "max_delta", DEFAULT_MAX_DELTA),
+    };
+    let max_removals = config::get(DAY, "max_removals", DEFAULT_MAX_REMOVALS);
+    let reports = parse_reports();
+    if args::flag("--verdicts") {
+        println!("(levels are numbered from 0)");
+        for (i, levels) in reports.iter().enumerate() {
+            let verdict = judge(levels, &rules, max_removals);
+            println!("{:>4}: {:<30} {}", i + 1, levels.iter().join(" "), verdict);
+        }
+        return;
+    }
+
+    println!("Part 1: {}", solve_p1(&reports, &rules));
+    println!("Part 2: {}", solve_p2(&reports, &rules, max_removals));
+}
+
+fn parse_reports() -> Vec<Vec<i32>> {
+    read_input_lines(DAY).iter()
+        .map(|line| line.split_whitespace().map(|s| s.parse::<i32>().unwrap()).collect())
+        .collect()
+}
+
+fn solve_p1(reports: &[Vec<i32>], rules: &Rules) -> usize {
+    get_safe_levels_count(reports, rules, 0)
+}
+
+fn solve_p2(reports: &[Vec<i32>], rules: &Rules, max_removals: usize) -> usize {
+    get_safe_levels_count(reports, rules, max_removals)
+}
+
+fn get_safe_levels_count(reports: &[Vec<i32>], rules: &Rules, max_removals: usize) -> usize {
+    reports.iter().filter(|levels| judge(levels, rules, max_removals).is_safe()).count()
 }
 
-fn solve_p1() -> i32 {
-    get_safe_levels_count(false)
+/// Allowed size of the change between adjacent levels, which must also all go the same way.
+#[derive(Clone, Copy, Debug)]
+struct Rules {
+    min_delta: i32,
+    max_delta: i32,
 }
 
-fn solve_p2() -> i32 {
-    get_safe_levels_count(true)
+impl Rules {
+    // `delta` is taken in the report's direction, i.e. negated for decreasing reports
+    fn allows(&self, delta: i32) -> bool {
+        (self.min_delta..=self.max_delta).contains(&delta)
+    }
 }
 
-fn get_safe_levels_count(allow_dampen: bool) -> i32 {
-    let lines = read_input_lines(DAY);
-    let mut count = 0;
-    'outer: for (_line_num, line) in lines.iter().enumerate() {
-        let nums: Vec<_> = line.split_whitespace().map(|s| s.parse::<i32>().unwrap()).collect();
-        let diffs = compute_diffs(&nums);
+/// Decides whether a report is safe, letting the Problem Dampener remove up to `max_removals`
+/// levels if it isn't.
+fn judge(levels: &[i32], rules: &Rules, max_removals: usize) -> Verdict {
+    let Err(fault) = find_fault(levels, rules) else {
+        return Verdict::Safe;
+    };
+    if max_removals > 0 {
+        if let Some(removed) = find_min_removals(levels, rules, max_removals) {
+            let removed = removed.into_iter().map(|i| (i, levels[i])).collect();
+            return Verdict::Dampened { removed, fault };
+        }
+    }
+    Verdict::Unsafe(fault)
+}
 
-        if are_diffs_safe(&diffs) {
-            count += 1;
-        } else if allow_dampen {
-            for i in 0..nums.len() {
-                let mut nums_modified = nums.clone();
-                nums_modified.remove(i);
-                let nums_modified = nums_modified;
-                let new_diffs = compute_diffs(&nums_modified);
-                if are_diffs_safe(&new_diffs) {
-                    count += 1;
-                    continue 'outer;
+// the direction is set by the first pair that moves, so a later pair going the other way is
+// what's reported
+fn find_fault(levels: &[i32], rules: &Rules) -> Result<(), Fault> {
+    let mut direction = 0;
+    for (index, (&from, &to)) in levels.iter().tuple_windows().enumerate() {
+        let delta = to - from;
+        let violation = if delta != 0 && direction != 0 && delta.signum() != direction {
+            Some(Violation::DirectionChange)
+        } else if delta.abs() < rules.min_delta {
+            Some(Violation::TooSmall(rules.min_delta))
+        } else if delta.abs() > rules.max_delta {
+            Some(Violation::TooLarge(rules.max_delta))
+        } else {
+            None
+        };
+        if let Some(violation) = violation {
+            return Err(Fault { index, from, to, violation });
+        }
+        if delta != 0 {
+            direction = delta.signum();
+        }
+    }
+    Ok(())
+}
+
+/// Fewest removals, up to `max_removals`, that make the report safe, as the indices removed.
+///
+/// For each direction, `cost[j]` is the fewest removals among the first `j` levels such that
+/// the levels kept up to and including `j` are safe. Two kept levels can only end up adjacent if
+/// at most `max_removals` levels between them were removed, so each `j` only looks back that
+/// far, which makes this O(n * k) rather than trying every combination of removals.
+fn find_min_removals(levels: &[i32], rules: &Rules, max_removals: usize) -> Option<Vec<usize>> {
+    let n = levels.len();
+    if n == 0 {
+        return Some(Vec::new());
+    }
+
+    let mut best: Option<Vec<usize>> = None;
+    for direction in [1, -1] {
+        let mut cost = vec![usize::MAX; n];
+        let mut prev_kept: Vec<Option<usize>> = vec![None; n];
+        for j in 0..n {
+            // keeping j as the first level means removing everything before it
+            if j <= max_removals {
+                cost[j] = j;
+            }
+            for i in j.saturating_sub(max_removals + 1)..j {
+                if cost[i] == usize::MAX || !rules.allows(direction * (levels[j] - levels[i])) {
+                    continue;
+                }
+                let c = cost[i] + (j - i - 1);
+                if c < cost[j] {
+                    cost[j] = c;
+                    prev_kept[j] = Some(i);
                 }
             }
         }
+
+        // ...and keeping j as the last level means removing everything after it
+        let last = (0..n)
+            .filter(|&j| cost[j] != usize::MAX && cost[j] + (n - 1 - j) <= max_removals)
+            .min_by_key(|&j| cost[j] + (n - 1 - j));
+        let Some(last) = last else {
+            continue;
+        };
+
+        let mut kept = vec![false; n];
+        let mut cur = Some(last);
+        while let Some(j) = cur {
+            kept[j] = true;
+            cur = prev_kept[j];
+        }
+        let removed: Vec<usize> = (0..n).filter(|&i| !kept[i]).collect();
+        if best.as_ref().is_none_or(|b| removed.len() < b.len()) {
+            best = Some(removed);
+        }
     }
-    count
+    best
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Violation {
+    TooSmall(i32),
+    TooLarge(i32),
+    DirectionChange,
 }
 
-fn compute_diffs(nums: &Vec<i32>) -> Vec<i32> {
-    nums.iter().zip(nums.iter().skip(1)).map(|(a, b)| b - a).collect()
+/// The first adjacent pair of levels that breaks the rules, starting at `index`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Fault {
+    index: usize,
+    from: i32,
+    to: i32,
+    violation: Violation,
 }
 
-fn are_diffs_safe(diffs: &Vec<i32>) -> bool {
-    let all_inside_range = diffs.iter().all(|&n| n >= -3 && n <= 3 && n != 0);
-    let all_asc = diffs.iter().all(|n| *n > 0);
-    let all_desc = diffs.iter().all(|n| *n < 0);
-    all_inside_range && (all_asc || all_desc)
+impl Display for Fault {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "levels {}-{} ({} -> {}) ", self.index, self.index + 1, self.from, self.to)?;
+        let delta = (self.to - self.from).abs();
+        match self.violation {
+            Violation::TooSmall(_) if delta == 0 => write!(f, "don't change"),
+            Violation::TooSmall(min) => write!(f, "change by {} (less than {})", delta, min),
+            Violation::TooLarge(max) => write!(f, "change by {} (more than {})", delta, max),
+            Violation::DirectionChange => write!(f, "change direction"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Verdict {
+    Safe,
+    /// Safe once the `(index, level)`s in `removed` are dropped; `fault` is what made it unsafe
+    /// to begin with.
+    Dampened { removed: Vec<(usize, i32)>, fault: Fault },
+    Unsafe(Fault),
+}
+
+impl Verdict {
+    fn is_safe(&self) -> bool {
+        !matches!(self, Verdict::Unsafe(_))
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Safe => write!(f, "safe"),
+            Verdict::Dampened { removed, fault } => {
+                let levels = removed.iter().map(|(i, level)| format!("{} ({})", i, level)).join(", ");
+                let noun = if removed.len() == 1 { "level" } else { "levels" };
+                write!(f, "safe after removing {} {}; originally {}", noun, levels, fault)
+            }
+            Verdict::Unsafe(fault) => write!(f, "unsafe: {}", fault),
+        }
+    }
 }
@@ -1,36 +1,245 @@
-use regex::Regex;
+use aoc2024_common::args;
 use aoc2024_common::file::read_input_string;
 
 const DAY: u32 = 3;
 
+const MAX_OPERAND_DIGITS: usize = 3;
+
+/// The instructions the memory can contain. Anything that doesn't exactly match one of these is
+/// corruption and gets skipped, so supporting a new instruction is just a matter of adding it
+/// here.
+const INSTRUCTIONS: &[Instruction] = &[
+    Instruction {
+        name: "mul",
+        operands: 2,
+        conditional: false,
+        exec: |machine, ops| {
+            if machine.enabled {
+                machine.total += ops[0] * ops[1];
+            }
+        },
+    },
+    Instruction { name: "do", operands: 0, conditional: true, exec: |machine, _| machine.enabled = true },
+    Instruction { name: "don't", operands: 0, conditional: true, exec: |machine, _| machine.enabled = false },
+];
+
 fn main() {
-    println!("Part 1: {}", solve_p1());
-    println!("Part 2: {}", solve_p2());
+    let input = read_input_string(DAY);
+    if args::flag("--trace") {
+        print!("{}", trace(&input));
+        return;
+    }
+
+    println!("Part 1: {}", solve_p1(&input));
+    println!("Part 2: {}", solve_p2(&input));
 }
 
-fn solve_p1() -> i32 {
-    let input = read_input_string(DAY);
-    process_muls(input.as_str())
+fn solve_p1(input: &str) -> i64 {
+    run(input, false)
 }
 
-fn solve_p2() -> i32 {
-    let do_dont_re = Regex::new(r"(?:^|do\(\))(.*?)(?:$|don't\(\))").unwrap();
+fn solve_p2(input: &str) -> i64 {
+    run(input, true)
+}
 
-    let input = read_input_string(DAY);
+fn run(input: &str, with_conditionals: bool) -> i64 {
+    let instructions: Vec<&Instruction> = INSTRUCTIONS.iter()
+        .filter(|instr| with_conditionals || !instr.conditional)
+        .collect();
+    let mut machine = Machine::new();
+    for scanned in Tokenizer::new(input, &instructions, false) {
+        if let Scanned::Token(token) = scanned {
+            (token.instruction.exec)(&mut machine, &token.operands);
+        }
+    }
+    machine.total
+}
+
+/// Lists every recognized instruction with its effect on both parts, then every near miss, i.e.
+/// something that starts out like an instruction call but isn't a valid one.
+fn trace(input: &str) -> String {
+    let instructions: Vec<&Instruction> = INSTRUCTIONS.iter().collect();
+    let mut p1 = Machine::new();
+    let mut p2 = Machine::new();
+    let mut near_misses = Vec::new();
+
+    let mut out = format!("{:>8}  {:<16} {:<9} {:>10} {:>10}\n", "Offset", "Instruction", "State", "Part 1", "Part 2");
+    for scanned in Tokenizer::new(input, &instructions, true) {
+        let token = match scanned {
+            Scanned::Token(token) => token,
+            Scanned::NearMiss(near_miss) => {
+                near_misses.push(near_miss);
+                continue;
+            }
+        };
 
-    let mut total = 0;
-        for (_, [inner]) in do_dont_re.captures_iter(&input).map(|c| c.extract()) {
-            total += process_muls(inner)
+        let (p1_before, p2_before, was_enabled) = (p1.total, p2.total, p2.enabled);
+        if !token.instruction.conditional {
+            (token.instruction.exec)(&mut p1, &token.operands);
         }
+        (token.instruction.exec)(&mut p2, &token.operands);
 
-    total
+        let state = match (was_enabled, p2.enabled) {
+            (false, true) => "enables",
+            (true, false) => "disables",
+            (true, true) => "enabled",
+            (false, false) => "disabled",
+        };
+        out += &format!(
+            "{:>8}  {:<16} {:<9} {:>10} {:>10}\n",
+            token.offset,
+            &input[token.offset..token.end],
+            state,
+            format_contribution(p1.total - p1_before),
+            format_contribution(p2.total - p2_before),
+        );
+    }
+    out += &format!("{:>8}  {:<16} {:<9} {:>10} {:>10}\n", "", "Total", "", p1.total, p2.total);
+
+    out += &format!("\nNear misses ({}):\n", near_misses.len());
+    for near_miss in &near_misses {
+        out += &format!(
+            "{:>8}  {:<16} {}\n",
+            near_miss.offset,
+            // the near miss may end partway into a multi-byte character
+            format!("{:?}", String::from_utf8_lossy(&input.as_bytes()[near_miss.offset..near_miss.end])),
+            near_miss.reason,
+        );
+    }
+    out
+}
+
+fn format_contribution(delta: i64) -> String {
+    if delta == 0 { "-".to_string() } else { format!("{:+}", delta) }
+}
+
+struct Instruction {
+    name: &'static str,
+    /// Number of operands, each a plain integer of 1 to `MAX_OPERAND_DIGITS` digits.
+    operands: usize,
+    /// Whether the instruction controls whether others take effect, which part 1 ignores.
+    conditional: bool,
+    exec: fn(&mut Machine, &[i64]),
 }
 
-fn process_muls(line: &str) -> i32 {
-    let mul_re = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
-    let mut total = 0;
-    for (_, [a, b]) in mul_re.captures_iter(&line).map(|c| c.extract()) {
-        total += a.parse::<i32>().unwrap() * b.parse::<i32>().unwrap();
+struct Machine {
+    enabled: bool,
+    total: i64,
+}
+
+impl Machine {
+    fn new() -> Self {
+        Self { enabled: true, total: 0 }
+    }
+}
+
+struct Token<'a> {
+    instruction: &'a Instruction,
+    operands: Vec<i64>,
+    /// Byte range of the instruction in the input.
+    offset: usize,
+    end: usize,
+}
+
+struct NearMiss {
+    offset: usize,
+    /// Just past the byte where parsing went wrong.
+    end: usize,
+    reason: String,
+}
+
+enum Scanned<'a> {
+    Token(Token<'a>),
+    NearMiss(NearMiss),
+}
+
+/// Lazily picks instructions out of the memory. The input is treated as one stream of bytes, so
+/// line breaks are just more corruption rather than resetting anything.
+struct Tokenizer<'a> {
+    input: &'a [u8],
+    pos: usize,
+    instructions: &'a [&'a Instruction],
+    report_near_misses: bool,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(input: &'a str, instructions: &'a [&'a Instruction], report_near_misses: bool) -> Self {
+        Self { input: input.as_bytes(), pos: 0, instructions, report_near_misses }
+    }
+
+    /// Tries to read `instr` at `start`. Failing after the name and an opening parenthesis (give
+    /// or take some whitespace) counts as a near miss; failing any earlier is just corruption.
+    fn try_read(&self, instr: &'a Instruction, start: usize) -> Result<Token<'a>, Option<NearMiss>> {
+        let mut pos = self.expect(start, instr.name.as_bytes()).ok_or(None)?;
+        let spaces = self.input[pos..].iter().take_while(|b| b.is_ascii_whitespace()).count();
+        if self.input.get(pos + spaces) != Some(&b'(') {
+            return Err(None);
+        }
+        if spaces > 0 {
+            return Err(Some(self.near_miss(start, pos + spaces, "whitespace before '('".to_string())));
+        }
+        pos += 1;
+
+        let mut operands = Vec::with_capacity(instr.operands);
+        for i in 0..instr.operands {
+            if i > 0 {
+                pos = self.expect(pos, b",")
+                    .ok_or_else(|| self.near_miss(start, pos, format!("expected ',' {}", self.describe_at(pos))))?;
+            }
+            let digits = self.input[pos..].iter().take_while(|b| b.is_ascii_digit()).count();
+            if digits == 0 {
+                return Err(Some(self.near_miss(start, pos, format!("expected operand {}", self.describe_at(pos)))));
+            }
+            if digits > MAX_OPERAND_DIGITS {
+                let reason = format!("operand has {} digits (at most {} allowed)", digits, MAX_OPERAND_DIGITS);
+                return Err(Some(self.near_miss(start, pos + digits - 1, reason)));
+            }
+            let operand = std::str::from_utf8(&self.input[pos..pos + digits]).unwrap().parse::<i64>().unwrap();
+            operands.push(operand);
+            pos += digits;
+        }
+        pos = self.expect(pos, b")")
+            .ok_or_else(|| self.near_miss(start, pos, format!("expected ')' {}", self.describe_at(pos))))?;
+        Ok(Token { instruction: instr, operands, offset: start, end: pos })
+    }
+
+    fn expect(&self, pos: usize, bytes: &[u8]) -> Option<usize> {
+        self.input[pos..].starts_with(bytes).then_some(pos + bytes.len())
+    }
+
+    fn near_miss(&self, start: usize, pos: usize, reason: String) -> NearMiss {
+        NearMiss { offset: start, end: (pos + 1).min(self.input.len()), reason }
+    }
+
+    fn describe_at(&self, pos: usize) -> String {
+        match self.input.get(pos) {
+            Some(&b) => format!("but found {:?}", b as char),
+            None => "but the input ended".to_string(),
+        }
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Scanned<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.input.len() {
+            let mut near_miss = None;
+            for instr in self.instructions {
+                match self.try_read(instr, self.pos) {
+                    Ok(token) => {
+                        self.pos = token.end;
+                        return Some(Scanned::Token(token));
+                    }
+                    Err(Some(miss)) if near_miss.is_none() => near_miss = Some(miss),
+                    Err(_) => {}
+                }
+            }
+            self.pos += 1;
+            if let Some(miss) = near_miss.filter(|_| self.report_near_misses) {
+                return Some(Scanned::NearMiss(miss));
+            }
+        }
+        None
     }
-    total
 }
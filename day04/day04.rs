@@ -1,118 +1,306 @@
-use std::iter;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use aoc2024_common::args;
 use aoc2024_common::file::read_input_lines;
 
 const DAY: u32 = 4;
 
-const P1_NEEDLE: &str = "XMAS";
-const P1_CTX_RADIUS: i32 = P1_NEEDLE.len() as i32 - 1;
-const P2_CTX_RADIUS: i32 = 1;
-const PAD_CHAR: char = '.';
+const P1_WORD: &str = "XMAS";
+const P2_TEMPLATE: &str = "M.S/.A./M.S";
+const WILDCARD: u8 = b'.';
+const ROW_SEPARATOR: char = '/';
+const HIDDEN_CHAR: u8 = b'.';
 
 fn main() {
-    println!("Part 1: {}", solve_p1());
-    println!("Part 2: {}", solve_p2());
+    let grid = Grid::new(&read_input_lines(DAY));
+    if let Some(words) = args::value("--words") {
+        let words: Vec<&str> = words.split(',').map(str::trim).filter(|w| !w.is_empty()).collect();
+        let matches = find_words(&grid, &words);
+        for m in &matches {
+            println!("{} at row {}, col {} going {}", m.word, m.start.0, m.start.1, m.direction);
+        }
+        println!("{} matches\n", matches.len());
+        print!("{}", highlight(&grid, matches.iter().flat_map(WordMatch::cells)));
+        return;
+    }
+    let templates = args::values("--template");
+    if !templates.is_empty() {
+        let transformed = args::flag("--rotations");
+        let mut cells = Vec::new();
+        for template in &templates {
+            let variants = variants_of(&Template::parse(template), transformed);
+            let matches = find_templates(&grid, &variants);
+            println!("{}: {} matches", template, matches.len());
+            cells.extend(matches.iter().flat_map(TemplateMatch::cells));
+        }
+        println!();
+        print!("{}", highlight(&grid, cells));
+        return;
+    }
+
+    println!("Part 1: {}", solve_p1(&grid));
+    println!("Part 2: {}", solve_p2(&grid));
 }
 
-fn solve_p1() -> i32 {
-    let lines = read_input_lines(DAY);
+fn solve_p1(grid: &Grid) -> usize {
+    find_words(grid, &[P1_WORD]).len()
+}
 
-    let mut count = 0;
-    for (row, line) in lines.iter().enumerate() {
-        for (col, c) in line.chars().enumerate() {
-            if c == P1_NEEDLE.chars().nth(0).unwrap() {
-                let context = get_context(&lines, P1_CTX_RADIUS, row as i32, col as i32);
-                count += find_word(&context);
-            }
+fn solve_p2(grid: &Grid) -> usize {
+    find_templates(grid, &variants_of(&Template::parse(P2_TEMPLATE), true)).len()
+}
+
+struct Grid {
+    rows: Vec<Vec<u8>>,
+}
+
+impl Grid {
+    fn new(lines: &[String]) -> Self {
+        Self { rows: lines.iter().map(|line| line.as_bytes().to_vec()).collect() }
+    }
+
+    fn get(&self, row: isize, col: isize) -> Option<u8> {
+        if row < 0 || col < 0 {
+            return None;
         }
+        self.rows.get(row as usize)?.get(col as usize).copied()
     }
+}
 
-    count
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
 }
 
-fn solve_p2() -> i32 {
-    let lines = read_input_lines(DAY);
+impl Direction {
+    const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
 
-    let mut count = 0;
-    for (row, line) in lines.iter().enumerate() {
-        for (col, c) in line.chars().enumerate() {
-            if c == 'A' {
-                let context = get_context(&lines, P2_CTX_RADIUS, row as i32, col as i32);
-                if contains_cross(&context) {
-                    count += 1;
+    /// (row, col) step
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+            Direction::Right => (0, 1),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (1, 0),
+            Direction::DownLeft => (1, -1),
+            Direction::Left => (0, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Direction::Up => "up",
+            Direction::UpRight => "up-right",
+            Direction::Right => "right",
+            Direction::DownRight => "down-right",
+            Direction::Down => "down",
+            Direction::DownLeft => "down-left",
+            Direction::Left => "left",
+            Direction::UpLeft => "up-left",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct WordMatch<'a> {
+    word: &'a str,
+    /// (row, col) of the word's first letter.
+    start: (usize, usize),
+    direction: Direction,
+}
+
+impl WordMatch<'_> {
+    /// Position of the word's `i`th letter.
+    fn cell_at(&self, i: usize) -> (usize, usize) {
+        let (dr, dc) = self.direction.delta();
+        (
+            (self.start.0 as isize + dr * i as isize) as usize,
+            (self.start.1 as isize + dc * i as isize) as usize,
+        )
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.word.len()).map(|i| self.cell_at(i))
+    }
+}
+
+/// Finds every occurrence of each word in any of the 8 directions, in reading order of where
+/// they start. Each occurrence is only counted once, so a palindrome read backwards over the
+/// same cells isn't another match, and neither is a one-letter word read in another direction.
+fn find_words<'a>(grid: &Grid, words: &[&'a str]) -> Vec<WordMatch<'a>> {
+    let mut unique_words: Vec<&str> = Vec::new();
+    for &word in words {
+        if !unique_words.contains(&word) {
+            unique_words.push(word);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut matches = Vec::new();
+    for (row, line) in grid.rows.iter().enumerate() {
+        for (col, &c) in line.iter().enumerate() {
+            for &word in &unique_words {
+                if word.as_bytes().first() != Some(&c) {
+                    continue;
+                }
+                let directions = if word.len() == 1 { &Direction::ALL[..1] } else { &Direction::ALL[..] };
+                for &direction in directions {
+                    if !word_matches(grid, word.as_bytes(), (row, col), direction) {
+                        continue;
+                    }
+                    let m = WordMatch { word, start: (row, col), direction };
+                    let end = m.cell_at(word.len() - 1);
+                    if seen.insert((word, m.start.min(end), m.start.max(end))) {
+                        matches.push(m);
+                    }
                 }
             }
         }
     }
+    matches
+}
+
+fn word_matches(grid: &Grid, word: &[u8], start: (usize, usize), direction: Direction) -> bool {
+    let (dr, dc) = direction.delta();
+    word.iter().enumerate().all(|(i, &expected)| {
+        let row = start.0 as isize + dr * i as isize;
+        let col = start.1 as isize + dc * i as isize;
+        grid.get(row, col) == Some(expected)
+    })
+}
+
+/// A rectangular pattern of letters, where `WILDCARD` matches anything.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Template {
+    rows: Vec<Vec<Option<u8>>>,
+}
+
+impl Template {
+    /// Rows are separated by `ROW_SEPARATOR`; short rows are padded out with wildcards.
+    fn parse(s: &str) -> Self {
+        let mut rows: Vec<Vec<Option<u8>>> = s.split(ROW_SEPARATOR)
+            .map(|row| row.bytes().map(|b| (b != WILDCARD).then_some(b)).collect())
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(width, None);
+        }
+        let template = Self { rows };
+        if template.letters().next().is_none() {
+            panic!("Template '{}' has no letters, so it would match anywhere", s);
+        }
+        template
+    }
 
-    count
-}
-
-fn get_context(lines: &Vec<String>, radius: i32, row: i32, col: i32) -> Vec<String> {
-    let start_row = i32::max(row - radius, 0);
-    let end_row = i32::min(row + radius, lines.len() as i32 - 1);
-    let start_col = i32::max(col - radius, 0);
-    let end_col = i32::min(col + radius, lines[0].len() as i32 - 1);
-
-    let pad_top = i32::abs_diff(row - radius, start_row) as usize;
-    let pad_bottom = i32::abs_diff(row + radius, end_row) as usize;
-    let pad_left = i32::abs_diff(col - radius, start_col) as usize;
-    let pad_right = i32::abs_diff(col + radius, end_col) as usize;
-
-    let base_context: Vec<_> = lines[start_row as usize..=end_row as usize].iter()
-        .map(|line| pad(&line[start_col as usize..=end_col as usize], pad_left, pad_right))
-        .collect();
-
-    let pad_line = pad("", radius as usize * 2 + 1, 0);
-    vec![
-        iter::repeat_n(pad_line.clone(), pad_top).collect(),
-        base_context,
-        iter::repeat_n(pad_line.clone(), pad_bottom).collect(),
-    ]
-        .into_iter()
-        .flatten()
-        .collect()
-}
-
-fn pad(s: &str, left: usize, right: usize) -> String {
-    iter::repeat_n(PAD_CHAR, left)
-        .chain(s.to_string().chars())
-        .chain(iter::repeat_n(PAD_CHAR, right).take(right))
-        .collect()
-}
-
-fn find_word(context: &Vec<String>) -> i32 {
-    return
-        find_word_in_dir(&context, -1,  0) + // up
-        find_word_in_dir(&context, -1,  1) + // up-right
-        find_word_in_dir(&context,  0,  1) + // right
-        find_word_in_dir(&context,  1,  1) + // down-right
-        find_word_in_dir(&context,  1,  0) + // down
-        find_word_in_dir(&context,  1, -1) + // down-left
-        find_word_in_dir(&context,  0, -1) + // left
-        find_word_in_dir(&context, -1, -1);  // up-left
-}
-
-fn find_word_in_dir(context: &Vec<String>, step_v: i32, step_h: i32) -> i32 {
-    for i in 1..P1_NEEDLE.len() as i32 {
-        let needle_char = P1_NEEDLE.chars().nth(i as usize).unwrap();
-        let cur_row = (P1_NEEDLE.len() as i32 - 1) + (step_v * i);
-        let cur_col = (P1_NEEDLE.len() as i32 - 1) + (step_h * i);
-        let actual_char = context[cur_row as usize].chars().nth(cur_col as usize).unwrap();
-        if needle_char != actual_char {
-            return 0;
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn width(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    /// Turned a quarter clockwise.
+    fn rotated(&self) -> Self {
+        let rows = (0..self.width())
+            .map(|c| (0..self.height()).rev().map(|r| self.rows[r][c]).collect())
+            .collect();
+        Self { rows }
+    }
+
+    /// Mirrored left to right.
+    fn reflected(&self) -> Self {
+        Self { rows: self.rows.iter().map(|row| row.iter().rev().copied().collect()).collect() }
+    }
+
+    /// Whether the template fits entirely within the grid at `(row, col)` with every letter
+    /// matching, so that wildcards can't hang off the edge.
+    fn matches_at(&self, grid: &Grid, row: usize, col: usize) -> bool {
+        let fits = grid.rows.get(row..row + self.height())
+            .is_some_and(|rows| rows.iter().all(|line| col + self.width() <= line.len()));
+        fits && self.letters().all(|((r, c), expected)| grid.get((row + r) as isize, (col + c) as isize) == Some(expected))
+    }
+
+    /// Offsets of the non-wildcard cells along with the letter each must be.
+    fn letters(&self) -> impl Iterator<Item = ((usize, usize), u8)> + '_ {
+        self.rows.iter().enumerate().flat_map(|(r, row)| {
+            row.iter().enumerate().filter_map(move |(c, &cell)| cell.map(|b| ((r, c), b)))
+        })
+    }
+}
+
+/// The template along with, if `transformed`, each of its distinct rotations and reflections.
+/// Symmetric templates have fewer than 8 of these, and each is only kept once so that no
+/// occurrence is counted twice.
+fn variants_of(template: &Template, transformed: bool) -> Vec<Template> {
+    let mut variants = vec![template.clone()];
+    if !transformed {
+        return variants;
+    }
+    for start in [template.clone(), template.reflected()] {
+        let mut t = start;
+        for _ in 0..4 {
+            if !variants.contains(&t) {
+                variants.push(t.clone());
+            }
+            t = t.rotated();
         }
     }
-    1
+    variants
+}
+
+struct TemplateMatch<'a> {
+    template: &'a Template,
+    /// (row, col) of the template's top left corner.
+    start: (usize, usize),
 }
 
-fn contains_cross(context: &Vec<String>) -> bool {
-    let top_left = context[0].chars().nth(0).unwrap();
-    let top_right = context[0].chars().nth(2).unwrap();
-    let bottom_left = context[2].chars().nth(0).unwrap();
-    let bottom_right = context[2].chars().nth(2).unwrap();
+impl TemplateMatch<'_> {
+    fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.template.letters().map(|((r, c), _)| (self.start.0 + r, self.start.1 + c))
+    }
+}
 
-    let s1 = [top_left, 'A', bottom_right];
-    let s2 = [top_right, 'A', bottom_left];
+fn find_templates<'a>(grid: &Grid, templates: &'a [Template]) -> Vec<TemplateMatch<'a>> {
+    let mut matches = Vec::new();
+    for (row, line) in grid.rows.iter().enumerate() {
+        for col in 0..line.len() {
+            for template in templates {
+                if template.matches_at(grid, row, col) {
+                    matches.push(TemplateMatch { template, start: (row, col) });
+                }
+            }
+        }
+    }
+    matches
+}
 
-    (s1 == ['M', 'A', 'S'] || s1 == ['S', 'A', 'M']) && (s2 == ['M', 'A', 'S'] || s2 == ['S', 'A', 'M'])
+/// The grid with every letter that isn't in one of `cells` blanked out.
+fn highlight(grid: &Grid, cells: impl IntoIterator<Item = (usize, usize)>) -> String {
+    let mut shown: Vec<Vec<u8>> = grid.rows.iter().map(|line| vec![HIDDEN_CHAR; line.len()]).collect();
+    for (row, col) in cells {
+        shown[row][col] = grid.rows[row][col];
+    }
+    shown.into_iter().map(|line| String::from_utf8_lossy(&line).to_string() + "\n").collect()
 }
@@ -1,6 +1,8 @@
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
 use itertools::Itertools;
+use aoc2024_common::args;
 use aoc2024_common::file::read_input_lines;
 
 const DAY: u32 = 5;
@@ -11,25 +13,48 @@ fn main() {
     let (rule_lines, seq_lines): (Vec<_>, Vec<_>) = lines.into_iter().partition(|line| line.contains("|"));
 
     let rules = get_rules(rule_lines);
+    if args::flag("--check") {
+        println!("(pages are numbered from 0)");
+        for (i, line) in seq_lines.iter().enumerate() {
+            let seq = parse_seq(line);
+            let validity = match find_violation(&rules, &seq) {
+                Some(violation) => format!("invalid: {}", violation),
+                None => "valid".to_string(),
+            };
+            println!("{:>4}: {:<70} {}; {}", i + 1, line, validity, find_order(&rules, &seq));
+        }
+        return;
+    }
+
     let (valid, invalid) = get_valid_invalid_seqs(&rules, seq_lines);
+    if args::flag("--repair") {
+        print!("{}", repair_report(&rules, &invalid));
+        return;
+    }
+
+    // the rules only give one correct order if they fix it
+    for seq in &invalid {
+        if let ambiguous @ PageOrder::Ambiguous { .. } = find_order(&rules, seq) {
+            eprintln!("Warning: {} in {}, so Part 2 may be wrong", ambiguous, seq.iter().join(","));
+        }
+    }
 
     println!("Part 1: {}", solve_p1(&valid));
     println!("Part 2: {}", solve_p2(&rules, &invalid));
 }
 
-fn solve_p1(valid_seqs: &Vec<Vec<i32>>) -> i32 {
+fn solve_p1(valid_seqs: &[Vec<i32>]) -> i32 {
     sum_middle_elements(valid_seqs)
 }
 
-fn solve_p2(rules: &HashMap<i32, Vec<i32>>, invalid_seqs: &Vec<Vec<i32>>) -> i32 {
+fn solve_p2(rules: &HashMap<i32, Vec<i32>>, invalid_seqs: &[Vec<i32>]) -> i32 {
     sum_middle_elements(
         &invalid_seqs.iter()
-            .map(|seq| seq.iter()
-                .copied()
-                .sorted_by(|&a, &b| { compare(rules, a, b) })
-                .collect::<Vec<_>>()
-            )
-            .collect()
+            .map(|seq| match find_order(rules, seq) {
+                PageOrder::Unique(order) | PageOrder::Ambiguous { order, .. } => order,
+                cyclic @ PageOrder::Cyclic(_) => panic!("Can't order {}, {}", seq.iter().join(","), cyclic),
+            })
+            .collect::<Vec<_>>()
     )
 }
 
@@ -44,8 +69,134 @@ fn get_rules(lines: Vec<String>) -> HashMap<i32, Vec<i32>> {
 
 fn get_valid_invalid_seqs(rules: &HashMap<i32, Vec<i32>>, lines: Vec<String>) -> (Vec<Vec<i32>>, Vec<Vec<i32>>) {
     lines.into_iter()
-        .map(|line| line.split(",").map(|s| s.parse::<i32>().unwrap()).collect::<Vec<_>>())
-        .partition(|seq| seq.is_sorted_by(|&a, &b| compare(rules, a, b) != Ordering::Greater))
+        .map(|line| parse_seq(&line))
+        .partition(|seq| find_violation(rules, seq).is_none())
+}
+
+fn parse_seq(line: &str) -> Vec<i32> {
+    line.split(",").map(|s| s.parse::<i32>().unwrap()).collect()
+}
+
+/// The first rule, if any, that `seq` breaks. Every pair of pages is checked rather than just
+/// neighbouring ones, so this doesn't depend on the rules being transitive.
+fn find_violation(rules: &HashMap<i32, Vec<i32>>, seq: &[i32]) -> Option<Violation> {
+    seq.iter().enumerate().tuple_combinations().find_map(|((early_index, &early), (late_index, &late))| {
+        (compare(rules, early, late) == Ordering::Greater).then_some(Violation { late, early, late_index, early_index })
+    })
+}
+
+/// Rule `late|early` is broken because `early` comes first, at `early_index`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Violation {
+    late: i32,
+    early: i32,
+    late_index: usize,
+    early_index: usize,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "rule {}|{} is broken by {} at {} coming before {} at {}",
+            self.late, self.early, self.early, self.early_index, self.late, self.late_index,
+        )
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum PageOrder {
+    Unique(Vec<i32>),
+    /// Nothing orders the pair of pages in `unordered`, directly or otherwise, so `order` is just
+    /// one of several that follow the rules.
+    Ambiguous { order: Vec<i32>, unordered: (i32, i32) },
+    /// The rules contradict each other, with each page here having to come before the next and
+    /// the last before the first.
+    Cyclic(Vec<i32>),
+}
+
+impl Display for PageOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PageOrder::Unique(_) => write!(f, "order is unique"),
+            PageOrder::Ambiguous { unordered: (a, b), .. } => write!(f, "order is ambiguous: nothing orders {} and {}", a, b),
+            PageOrder::Cyclic(cycle) => write!(f, "rules are cyclic: {} -> {}", cycle.iter().join(" -> "), cycle[0]),
+        }
+    }
+}
+
+/// Orders the pages of `seq` by only the rules between them. Pages nothing orders relative to
+/// each other keep their original relative order.
+fn find_order(rules: &HashMap<i32, Vec<i32>>, seq: &[i32]) -> PageOrder {
+    let (order, unordered) = topological_order(seq, &rule_successors(rules, seq));
+    if order.len() < seq.len() {
+        let order: Vec<i32> = order.iter().map(|&i| seq[i]).collect();
+        return PageOrder::Cyclic(find_cycle(rules, seq, &order));
+    }
+    let order = order.iter().map(|&i| seq[i]).collect();
+    match unordered {
+        Some((a, b)) => PageOrder::Ambiguous { order, unordered: (seq[a], seq[b]) },
+        None => PageOrder::Unique(order),
+    }
+}
+
+/// For each position in `seq`, the positions of the pages the rules say must come after it.
+fn rule_successors(rules: &HashMap<i32, Vec<i32>>, seq: &[i32]) -> Vec<Vec<usize>> {
+    seq.iter()
+        .map(|page| {
+            rules.get(page)
+                .map(|after| seq.iter().positions(|p| after.contains(p)).collect())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Kahn's algorithm over positions, taking whichever ready one comes first. Also returns the
+/// first pair found that nothing orders. Positions on or behind a cycle are left out.
+fn topological_order(seq: &[i32], successors: &[Vec<usize>]) -> (Vec<usize>, Option<(usize, usize)>) {
+    let mut in_degree = vec![0; seq.len()];
+    for j in successors.iter().flatten() {
+        in_degree[*j] += 1;
+    }
+
+    let mut ready: BTreeSet<usize> = (0..seq.len()).filter(|&i| in_degree[i] == 0).collect();
+    let mut order = Vec::with_capacity(seq.len());
+    let mut unordered = None;
+    while let Some(i) = ready.pop_first() {
+        if let Some(&other) = ready.first() {
+            unordered.get_or_insert((i, other));
+        }
+        order.push(i);
+        for &j in &successors[i] {
+            in_degree[j] -= 1;
+            if in_degree[j] == 0 {
+                ready.insert(j);
+            }
+        }
+    }
+    (order, unordered)
+}
+
+// every page Kahn's algorithm couldn't place still has an unplaced page that must come before it,
+// so following those back from any of them eventually runs into a cycle
+fn find_cycle(rules: &HashMap<i32, Vec<i32>>, seq: &[i32], placed: &[i32]) -> Vec<i32> {
+    let stuck: Vec<i32> = seq.iter().copied().filter(|p| !placed.contains(p)).collect();
+    let predecessor = |page: i32| {
+        *stuck.iter()
+            .find(|&&p| rules.get(&p).is_some_and(|after| after.contains(&page)))
+            .expect("an unplaced page has no unplaced predecessor")
+    };
+
+    let mut path = vec![stuck[0]];
+    loop {
+        let prev = predecessor(*path.last().unwrap());
+        if let Some(start) = path.iter().position(|&p| p == prev) {
+            let mut cycle = path.split_off(start);
+            cycle.reverse();
+            return cycle;
+        }
+        path.push(prev);
+    }
 }
 
 fn compare(rules: &HashMap<i32, Vec<i32>>, a: i32, b: i32) -> Ordering {
@@ -58,7 +209,157 @@ fn compare(rules: &HashMap<i32, Vec<i32>>, a: i32, b: i32) -> Ordering {
     }
 }
 
-fn sum_middle_elements(seq: &Vec<Vec<i32>>) -> i32 {
+/// Repairs each invalid update with as few page moves as possible, ordering it topologically
+/// under just the rules between its pages.
+fn repair_report(rules: &HashMap<i32, Vec<i32>>, invalid_seqs: &[Vec<i32>]) -> String {
+    let mut out = "(pages are numbered from 0)\n".to_string();
+    let mut repaired = Vec::new();
+    let mut total_moves = 0;
+    for seq in invalid_seqs {
+        out += &format!("{}\n", seq.iter().join(","));
+        if let cyclic @ PageOrder::Cyclic(_) = find_order(rules, seq) {
+            out += &format!("  can't be repaired, {}\n\n", cyclic);
+            continue;
+        }
+        let (order, moves) = repair(rules, seq);
+        let noun = if moves.len() == 1 { "move" } else { "moves" };
+        out += &format!("  -> {} ({} {})\n", order.iter().join(","), moves.len(), noun);
+        for m in &moves {
+            out += &format!("     {}\n", m);
+        }
+        out += "\n";
+        total_moves += moves.len();
+        repaired.push(order);
+    }
+    out += &format!("Repaired {} of {} updates with {} moves\n", repaired.len(), invalid_seqs.len(), total_moves);
+    out += &format!("Part 2: {}\n", sum_middle_elements(&repaired));
+    out
+}
+
+/// Takes one page out and puts it back in elsewhere, with positions as they are at the time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Move {
+    page: i32,
+    from: usize,
+    to: usize,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {} from {} to {}", self.page, self.from, self.to)
+    }
+}
+
+/// An order following the rules that `seq`, whose rules mustn't be cyclic, can be turned into
+/// with the fewest moves, along with those moves. The largest set of pages that can stay put is
+/// kept in place and the order is built around them, so every other page moves exactly once.
+fn repair(rules: &HashMap<i32, Vec<i32>>, seq: &[i32]) -> (Vec<i32>, Vec<Move>) {
+    let mut successors = rule_successors(rules, seq);
+    let fixed = find_fixed_pages(&transitive_closure(&successors));
+
+    // the fixed pages keep their relative order, which the rules allow since none of them has
+    // to come before one it's currently after
+    let fixed_positions: Vec<usize> = (0..seq.len()).filter(|&i| fixed[i]).collect();
+    for (&a, &b) in fixed_positions.iter().tuple_windows() {
+        successors[a].push(b);
+    }
+    let (order, _) = topological_order(seq, &successors);
+    debug_assert_eq!(order.len(), seq.len());
+    let stays: Vec<bool> = order.iter().map(|&i| fixed[i]).collect();
+    let order: Vec<i32> = order.iter().map(|&i| seq[i]).collect();
+
+    // placing the moved pages in order, each right after the page that precedes it in `order`,
+    // never disturbs the ones already in place
+    let mut current = seq.to_vec();
+    let mut moves = Vec::new();
+    for (i, &page) in order.iter().enumerate() {
+        if stays[i] {
+            continue;
+        }
+        let from = current.iter().position(|&p| p == page).unwrap();
+        current.remove(from);
+        let to = if i == 0 { 0 } else { current.iter().position(|&p| p == order[i - 1]).unwrap() + 1 };
+        current.insert(to, page);
+        moves.push(Move { page, from, to });
+    }
+    debug_assert_eq!(current, order);
+    (order, moves)
+}
+
+/// `reach[i][j]` is whether the page at `i` has to come before the one at `j`, directly or
+/// through other pages.
+fn transitive_closure(successors: &[Vec<usize>]) -> Vec<Vec<bool>> {
+    let n = successors.len();
+    let mut reach = vec![vec![false; n]; n];
+    for (start, row) in reach.iter_mut().enumerate() {
+        let mut stack = successors[start].clone();
+        while let Some(i) = stack.pop() {
+            if !row[i] {
+                row[i] = true;
+                stack.extend(&successors[i]);
+            }
+        }
+    }
+    reach
+}
+
+/// Largest set of positions whose pages can all stay where they are, i.e. where no page has to
+/// come before another that's currently ahead of it.
+///
+/// Pairs in the wrong order form a partial order of their own (if `a` has to follow `b` which has
+/// to follow `c`, `a` has to follow `c`), and a set that can stay is an antichain of it. By
+/// Dilworth's theorem the largest antichain has as many pages as there are pages minus a maximum
+/// matching of the wrong pairs, and König's theorem picks it out of the matching.
+fn find_fixed_pages(reach: &[Vec<bool>]) -> Vec<bool> {
+    let n = reach.len();
+    // i is ahead of j but has to come after it
+    let wrong = |i: usize, j: usize| i < j && reach[j][i];
+
+    // Kuhn's algorithm, matching earlier positions on the left to later ones on the right
+    let mut left_of: Vec<Option<usize>> = vec![None; n];
+    for i in 0..n {
+        augment(i, &wrong, &mut vec![false; n], &mut left_of);
+    }
+
+    // everything reachable by alternating paths from the unmatched left side
+    let mut left_seen = vec![true; n];
+    for &i in left_of.iter().flatten() {
+        left_seen[i] = false;
+    }
+    let mut right_seen = vec![false; n];
+    let mut stack: Vec<usize> = (0..n).filter(|&i| left_seen[i]).collect();
+    while let Some(i) = stack.pop() {
+        for j in 0..n {
+            if !wrong(i, j) || right_seen[j] {
+                continue;
+            }
+            right_seen[j] = true;
+            if let Some(k) = left_of[j].filter(|&k| !left_seen[k]) {
+                left_seen[k] = true;
+                stack.push(k);
+            }
+        }
+    }
+
+    let fixed: Vec<bool> = (0..n).map(|i| left_seen[i] && !right_seen[i]).collect();
+    debug_assert_eq!(fixed.iter().filter(|&&f| f).count(), n - left_of.iter().flatten().count());
+    fixed
+}
+
+fn augment(i: usize, wrong: &impl Fn(usize, usize) -> bool, seen: &mut [bool], left_of: &mut [Option<usize>]) -> bool {
+    for j in 0..left_of.len() {
+        if wrong(i, j) && !seen[j] {
+            seen[j] = true;
+            if left_of[j].is_none_or(|k| augment(k, wrong, seen, left_of)) {
+                left_of[j] = Some(i);
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn sum_middle_elements(seq: &[Vec<i32>]) -> i32 {
     let mut total = 0;
     for seq in seq {
         total += seq[seq.len() / 2];
@@ -1,9 +1,11 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::mpsc;
 use std::thread;
+use log::trace;
 use workerpool::Pool;
 use workerpool::thunk::{Thunk, ThunkWorker};
 use aoc2024_common::file::read_input_lines;
+use aoc2024_common::logging;
 
 const DAY: u32 = 6;
 
@@ -11,6 +13,7 @@ const OBSTACLE_CHAR: char = '#';
 const INITIAL_POS_CHAR: char = '^';
 
 fn main() {
+    logging::init();
     let params = get_world_parameters();
     println!("Part 1: {}", solve_p1(&params));
     println!("Part 2: {}", solve_p2(&params));
@@ -97,7 +100,7 @@ fn do_simulation(params: &WorldParameters) -> Result<i32, ()> {
     while cur_pos.0 >= 0 && cur_pos.0 < params.width &&
         cur_pos.1 >= 0 && cur_pos.1 < params.height {
         (cur_pos, cur_dir) = do_step(params, cur_pos, cur_dir);
-        //println!("Moved to {:?}", cur_pos);
+        trace!("Moved to {:?}", cur_pos);
         if visited_dirs.get(&cur_pos).map(|&d| d == cur_dir).unwrap_or(false) {
             return Err(())
         }
@@ -1,23 +1,26 @@
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap};
 use std::ops::Bound;
+use aoc2024_common::diff::{self, DiffConfig};
 use aoc2024_common::file::read_input_string;
 use itertools;
 use itertools::Itertools;
 
+const DAY: u32 = 9;
+
 fn main() {
-    println!("Part 1: {}", solve_p1());
-    println!("Part 2: {}", solve_p2());
+    if let Some(cfg) = DiffConfig::from_args() {
+        diff::run(DAY, "part 2 (block map vs FreeSpace)", &cfg, solve_p2_naive, solve_p2);
+        return;
+    }
+
+    let input = read_input_string(DAY);
+    println!("Part 1: {}", solve_p1(&input));
+    println!("Part 2: {}", solve_p2(&input));
 }
 
-fn solve_p1() -> u64 {
-    // add phantom free space at end to allow clean division into 2-element steps
-    let input = read_input_string(9) + "0";
-    let mut map = Vec::new();
-    for (file_id, (file_run_c, space_run_c)) in input.chars().tuples().enumerate() {
-        map.extend(vec![Some(file_id); file_run_c as usize - '0' as usize]);
-        map.extend(vec![None; space_run_c as usize - '0' as usize]);
-    }
+fn solve_p1(input: &str) -> u64 {
+    let mut map = build_block_map(input);
 
     let mut prev_first_free = 0;
     for cur_block_index in (0..map.len()).rev() {
@@ -32,19 +35,12 @@ fn solve_p1() -> u64 {
         map[cur_block_index] = None;
     }
 
-    let mut checksum = 0;
-    for (block_index, file_id_opt) in map.iter().enumerate() {
-        if let Some(file_id) = file_id_opt {
-            checksum += (block_index as u64 * *file_id as u64) as u64;
-        }
-    }
-
-    checksum
+    compute_block_checksum(&map)
 }
 
-fn solve_p2() -> u64 {
+fn solve_p2(input: &str) -> u64 {
     // add phantom free space at end to allow clean division into 2-element steps
-    let input = read_input_string(9) + "0";
+    let input = input.trim().to_string() + "0";
     let mut files = Vec::new();
     let mut free_space = FreeSpace::new();
     let mut cur_block_off = 0;
@@ -79,6 +75,61 @@ fn solve_p2() -> u64 {
     checksum
 }
 
+// straightforward block-by-block version of part 2, used as the reference for --diff
+fn solve_p2_naive(input: &str) -> u64 {
+    let mut map = build_block_map(input);
+    let max_file_id = map.iter().flatten().copied().max().unwrap_or(0);
+
+    for file_id in (0..=max_file_id).rev() {
+        let file_start = map.iter().position(|b| *b == Some(file_id)).unwrap();
+        let file_len = map[file_start..].iter().take_while(|b| **b == Some(file_id)).count();
+
+        let mut run_start = 0;
+        let mut run_len = 0;
+        for block_index in 0..file_start {
+            if map[block_index].is_some() {
+                run_len = 0;
+                continue;
+            }
+            if run_len == 0 {
+                run_start = block_index;
+            }
+            run_len += 1;
+            if run_len == file_len {
+                for i in 0..file_len {
+                    map[run_start + i] = Some(file_id);
+                    map[file_start + i] = None;
+                }
+                break;
+            }
+        }
+    }
+
+    compute_block_checksum(&map)
+}
+
+fn build_block_map(input: &str) -> Vec<Option<usize>> {
+    // add phantom free space at end to allow clean division into 2-element steps
+    let input = input.trim().to_string() + "0";
+    let mut map = Vec::new();
+    for (file_id, (file_run_c, space_run_c)) in input.chars().tuples().enumerate() {
+        map.extend(vec![Some(file_id); file_run_c as usize - '0' as usize]);
+        map.extend(vec![None; space_run_c as usize - '0' as usize]);
+    }
+    map
+}
+
+fn compute_block_checksum(map: &[Option<usize>]) -> u64 {
+    let mut checksum = 0;
+    for (block_index, file_id_opt) in map.iter().enumerate() {
+        if let Some(file_id) = file_id_opt {
+            checksum += block_index as u64 * *file_id as u64;
+        }
+    }
+
+    checksum
+}
+
 #[derive(Clone, Copy, Debug)]
 struct FileInfo {
     id: u32,
@@ -4,35 +4,43 @@ use std::io::Write;
 use std::ops::{Add, Mul, Rem};
 use std::path::PathBuf;
 use std::sync::mpsc;
+use std::time::Duration;
+use aoc2024_common::budget::{self, Budget, Exhausted};
+use aoc2024_common::config;
 use aoc2024_common::file::read_input_lines;
 use itertools::Itertools;
 use workerpool::Pool;
 use workerpool::thunk::{Thunk, ThunkWorker};
 
-const WIDTH: i64 = 101;
-const HEIGHT: i64 = 103;
+const DAY: u32 = 14;
+
+const DEFAULT_WIDTH: i64 = 101;
+const DEFAULT_HEIGHT: i64 = 103;
 const PART_1_ITERATIONS: i64 = 100;
 const PART_2_ITERATIONS: i64 = 9999; // takes ~45s on my Ryzen 5800X
 const PART_2_ITERATIONS_SKIP: i64 = 0;
 
 fn main() {
+    let budget = Budget::for_day(DAY);
     let robots = parse_robots();
-    println!("Part 1: {}", solve_p1(&robots));
-    solve_p2(&robots);
+    let bounds = (config::get(DAY, "width", DEFAULT_WIDTH), config::get(DAY, "height", DEFAULT_HEIGHT));
+    println!("Part 1: {}", solve_p1(&robots, bounds));
+    solve_p2(&robots, bounds, &budget);
     println!("Part 2: See file explorer :^)");
 }
 
-fn solve_p1(robots: &Vec<RobotDef>) -> u64 {
+fn solve_p1(robots: &[RobotDef], bounds: (i64, i64)) -> u64 {
+    let (width, height) = bounds;
     let mut robot_positions: Vec<Vector2l> = Vec::new();
     for robot in robots {
-        let new_pos = (((robot.pos + robot.vel * PART_1_ITERATIONS) % (WIDTH, HEIGHT)) + (WIDTH, HEIGHT)) % (WIDTH, HEIGHT);
+        let new_pos = (((robot.pos + robot.vel * PART_1_ITERATIONS) % bounds) + bounds) % bounds;
         robot_positions.push(new_pos);
     }
 
     let quads: Vec<_> = robot_positions
         .into_iter()
         .map(|pos| {
-            (pos, get_quadrant(WIDTH, HEIGHT, pos))
+            (pos, get_quadrant(width, height, pos))
         })
         .collect();
     let quad_counts: Vec<_> = quads.into_iter()
@@ -44,7 +52,8 @@ fn solve_p1(robots: &Vec<RobotDef>) -> u64 {
     quad_counts.into_iter().map(|(_, count)| count as u64).product()
 }
 
-fn solve_p2(robots: &Vec<RobotDef>) -> u64 {
+fn solve_p2(robots: &[RobotDef], bounds: (i64, i64), budget: &Budget) -> u64 {
+    let (width, height) = bounds;
     let images_dir_path = PathBuf::from("./part2_images");
     if !images_dir_path.exists() {
         fs::create_dir(&images_dir_path).expect("Failed to create images directory");
@@ -54,12 +63,15 @@ fn solve_p2(robots: &Vec<RobotDef>) -> u64 {
     let pool = Pool::<ThunkWorker<()>>::new(worker_count.get());
     let (tx, rx) = mpsc::channel();
     for i in PART_2_ITERATIONS_SKIP..=PART_2_ITERATIONS {
-        let robots_owned = robots.clone();
+        if let Err(reason) = budget.check() {
+            budget::give_up(&reason, &format!("queued {} renders", i - PART_2_ITERATIONS_SKIP));
+        }
+        let robots_owned = robots.to_vec();
         let bmp_file_path = images_dir_path.join(format!("{:06}.bmp", i));
         pool.execute_to(tx.clone(), Thunk::of(move || {
             let mut bmp_file = File::create(&bmp_file_path).expect("Failed to create BMP file");
 
-            let file_size = 32 + WIDTH * HEIGHT;
+            let file_size = 32 + width * height;
             let bmp_header_bytes: [u8; 14] = [
                 0x42, 0x4D, // magic
                 (file_size % 0xFF) as u8,
@@ -72,8 +84,8 @@ fn solve_p2(robots: &Vec<RobotDef>) -> u64 {
             ];
             let dib_header_bytes: [u8; 12] = [
                 0x0C, 0x00, 0x00, 0x00, // DIB header length
-                (WIDTH % 0xFF) as u8, ((WIDTH >> 8) % 0xFF) as u8,
-                ((-HEIGHT as i16) % 0xFF) as u8, (((-HEIGHT as i16) >> 8) % 0xFF) as u8,
+                (width % 0xFF) as u8, ((width >> 8) % 0xFF) as u8,
+                ((-height as i16) % 0xFF) as u8, (((-height as i16) >> 8) % 0xFF) as u8,
                 0x01, 0x00, // color planes count
                 0x01, 0x00, // BPP
             ];
@@ -85,19 +97,18 @@ fn solve_p2(robots: &Vec<RobotDef>) -> u64 {
             bmp_file.write(&dib_header_bytes).unwrap();
             bmp_file.write(&color_table_bytes).unwrap();
 
-            const PIXEL_ROW_LEN: usize = ((WIDTH + (32 - (WIDTH % 32))) / 8) as usize;
-            const PIXEL_DATA_LEN: usize = PIXEL_ROW_LEN * HEIGHT as usize;
-            let mut pixel_data = [0u8; PIXEL_DATA_LEN];
+            let pixel_row_len = ((width + (32 - (width % 32))) / 8) as usize;
+            let mut pixel_data = vec![0u8; pixel_row_len * height as usize];
 
             let mut robot_positions: Vec<Vector2l> = Vec::new();
             for robot in robots_owned {
-                let new_pos = (((robot.pos + robot.vel * i) % (WIDTH, HEIGHT)) + (WIDTH, HEIGHT)) % (WIDTH, HEIGHT);
+                let new_pos = (((robot.pos + robot.vel * i) % bounds) + bounds) % bounds;
                 robot_positions.push(new_pos);
             }
 
-            for i in 0..HEIGHT as usize {
-                for j in 0..WIDTH as usize {
-                    let byte_offset = i * PIXEL_ROW_LEN + j / 8;
+            for i in 0..height as usize {
+                for j in 0..width as usize {
+                    let byte_offset = i * pixel_row_len + j / 8;
                     let bit_offset = 7 - j as u8 % 8;
                     let has_robot = robot_positions.iter()
                         .any(|&pos| pos == Vector2l { x: j as i64, y: i as i64 });
@@ -113,13 +124,20 @@ fn solve_p2(robots: &Vec<RobotDef>) -> u64 {
         }));
     }
 
-    _ = rx.iter().take((PART_2_ITERATIONS - PART_2_ITERATIONS_SKIP + 1) as usize).collect::<Vec<_>>();
+    // wait for the renders, but stop at the deadline rather than after all of them
+    let total = (PART_2_ITERATIONS - PART_2_ITERATIONS_SKIP + 1) as usize;
+    for rendered in 0..total {
+        if rx.recv_timeout(budget.remaining().unwrap_or(Duration::MAX)).is_err() {
+            let reason = Exhausted::Time(budget.limit().unwrap_or_default());
+            budget::give_up(&reason, &format!("rendered {}/{} images to {}", rendered, total, images_dir_path.display()));
+        }
+    }
 
     0
 }
 
 fn parse_robots() -> Vec<RobotDef> {
-    read_input_lines(14).iter()
+    read_input_lines(DAY).iter()
         .map(|line| {
             line.split(" ")
                 .map(|s| {
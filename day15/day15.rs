@@ -1,7 +1,10 @@
 use std::collections::HashSet;
+use log::{debug, log_enabled, trace, Level};
 use aoc2024_common::file::read_input_lines_preserve_blank;
+use aoc2024_common::logging;
 
 fn main() {
+    logging::init();
     let (tiles, directions) = parse_input();
     println!("Part 1: {}", solve_p1(&tiles, &directions));
     println!("Part 2: {}", solve_p2(&tiles, &directions));
@@ -9,11 +12,12 @@ fn main() {
 
 fn solve_p1(tiles: &TileInfo, directions: &Vec<Direction>) -> u64 {
     let mut new_tiles = tiles.clone();
-    //print_tiles(&new_tiles);
+    log_tiles("Initial state", &new_tiles);
     for i in 0..directions.len() {
         let dir = directions[i];
         try_move(&mut new_tiles, dir);
     }
+    log_tiles("Final state", &new_tiles);
 
     new_tiles.boxes_l.iter().map(|(x, y)| (y * 100 + x) as u64).sum()
 }
@@ -27,10 +31,12 @@ fn solve_p2(tiles: &TileInfo, directions: &Vec<Direction>) -> u64 {
         boxes_l: tiles.boxes_l.iter().map(|&(x, y)| (x * 2, y)).collect(),
         boxes_r: tiles.boxes_l.iter().map(|&(x, y)| (x * 2 + 1, y)).collect(),
     };
+    log_tiles("Initial state", &transformed_tiles);
     for i in 0..directions.len() {
         let dir = directions[i];
         try_move(&mut transformed_tiles, dir);
     }
+    log_tiles("Final state", &transformed_tiles);
 
     transformed_tiles.boxes_l.iter().map(|(x, y)| (y * 100 + x) as u64).sum()
 }
@@ -82,6 +88,9 @@ fn try_move(tiles: &mut TileInfo, dir: Direction) {
     }
 
     tiles.cur_pos = (tiles.cur_pos.0 + delta.0, tiles.cur_pos.1 + delta.1);
+    if log_enabled!(Level::Trace) {
+        trace!("Moved {:?}:\n{}", dir, format_tiles(tiles));
+    }
 }
 
 fn parse_input() -> (TileInfo, Vec<Direction>) {
@@ -118,26 +127,32 @@ fn parse_input() -> (TileInfo, Vec<Direction>) {
     (tiles, dirs)
 }
 
-#[allow(unused)]
-fn print_tiles(tiles: &TileInfo) {
-    let box_l_repr = if tiles.boxes_r.is_empty() { "O" } else { "[" };
+fn log_tiles(label: &str, tiles: &TileInfo) {
+    if log_enabled!(Level::Debug) {
+        debug!("{}:\n{}", label, format_tiles(tiles));
+    }
+}
+
+fn format_tiles(tiles: &TileInfo) -> String {
+    let box_l_repr = if tiles.boxes_r.is_empty() { 'O' } else { '[' };
+    let mut out = String::new();
     for i in 0..tiles.height {
         for j in 0..tiles.width {
             if tiles.boxes_l.contains(&(j as i64, i as i64)) {
-                print!("{}", box_l_repr);
+                out.push(box_l_repr);
             } else if tiles.boxes_r.contains(&(j as i64, i as i64)) {
-                print!("]");
+                out.push(']');
             } else if tiles.walls.contains(&(j as i64, i as i64)) {
-                print!("#");
+                out.push('#');
             } else if tiles.cur_pos == (j as i64, i as i64) {
-                print!("@");
+                out.push('@');
             } else {
-                print!(".");
+                out.push('.');
             }
         }
-        println!();
+        out.push('\n');
     }
-    println!();
+    out
 }
 
 #[derive(Clone, Debug, Default)]
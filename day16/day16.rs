@@ -1,14 +1,32 @@
-use std::cmp::Ordering;
+use std::cmp::{Ordering, Reverse};
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::hash::{Hash, Hasher};
 use itertools::Itertools;
-use aoc2024_common::file::read_input_lines;
+use aoc2024_common::diff::{self, DiffConfig};
+use aoc2024_common::file::read_input_string;
+
+const DAY: u32 = 16;
 
 const MOVE_POINTS: u64 = 1;
 const TURN_POINTS: u64 = 1000;
 
 fn main() {
-    let maze = parse_input();
+    if let Some(cfg) = DiffConfig::from_args() {
+        diff::run(
+            DAY,
+            "parts 1 and 2 (Dijkstra vs A* and DFS)",
+            &cfg,
+            |input| solve_dijkstra(&parse_input(input)),
+            |input| {
+                let maze = parse_input(input);
+                let min_score = solve_p1(&maze);
+                (min_score, solve_p2(&maze, min_score))
+            },
+        );
+        return;
+    }
+
+    let maze = parse_input(&read_input_string(DAY));
     let min_score = solve_p1(&maze);
     println!("Part 1: {}", min_score);
     println!("Part 2: {}", solve_p2(&maze, min_score));
@@ -160,8 +178,73 @@ fn solve_p2(maze: &MazeDef, min_score: u64) -> u64 {
     best_path_tiles.len() as u64
 }
 
-fn parse_input() -> MazeDef {
-    let lines = read_input_lines(16);
+// plain Dijkstra from both ends, used as the reference for --diff: a tile is on a best path iff
+// the forward and backward distances through it add up to the minimum score
+fn solve_dijkstra(maze: &MazeDef) -> (u64, u64) {
+    fn get_distances(maze: &MazeDef, starts: &[((i32, i32), Direction)]) -> HashMap<((i32, i32), Direction), u64> {
+        let mut dist = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        for &start in starts {
+            dist.insert(start, 0);
+            queue.push(Reverse((0, start)));
+        }
+        while let Some(Reverse((cur_score, (cur_pos, cur_dir)))) = queue.pop() {
+            if cur_score > dist[&(cur_pos, cur_dir)] {
+                continue;
+            }
+            let move_delta = cur_dir.get_delta();
+            let turn_dirs = if cur_dir == Direction::North || cur_dir == Direction::South {
+                [Direction::East, Direction::West]
+            } else {
+                [Direction::North, Direction::South]
+            };
+            let neighbors = [
+                (((cur_pos.0 + move_delta.0, cur_pos.1 + move_delta.1), cur_dir), MOVE_POINTS),
+                ((cur_pos, turn_dirs[0]), TURN_POINTS),
+                ((cur_pos, turn_dirs[1]), TURN_POINTS),
+            ];
+            for (neighbor, weight) in neighbors {
+                if maze.walls.contains(&neighbor.0) {
+                    continue;
+                }
+                let new_score = cur_score + weight;
+                if new_score < *dist.get(&neighbor).unwrap_or(&u64::MAX) {
+                    dist.insert(neighbor, new_score);
+                    queue.push(Reverse((new_score, neighbor)));
+                }
+            }
+        }
+        dist
+    }
+
+    let all_dirs = [Direction::North, Direction::West, Direction::South, Direction::East];
+
+    let from_start = get_distances(maze, &[(maze.start, Direction::East)]);
+    let min_score = all_dirs.iter()
+        .filter_map(|&dir| from_start.get(&(maze.goal, dir)))
+        .copied()
+        .min()
+        .expect("No valid path found");
+
+    // walking backwards from the goal means facing the opposite way
+    let goal_states = all_dirs.iter()
+        .filter(|&&dir| from_start.get(&(maze.goal, dir)) == Some(&min_score))
+        .map(|&dir| (maze.goal, dir.opposite()))
+        .collect::<Vec<_>>();
+    let from_goal = get_distances(maze, &goal_states);
+
+    let best_tiles = from_start.iter()
+        .filter(|&(&(pos, dir), &score)| {
+            from_goal.get(&(pos, dir.opposite())).is_some_and(|&rev_score| score + rev_score == min_score)
+        })
+        .map(|(&(pos, _), _)| pos)
+        .collect::<HashSet<_>>();
+
+    (min_score, best_tiles.len() as u64)
+}
+
+fn parse_input(input: &str) -> MazeDef {
+    let lines: Vec<_> = input.split("\n").filter(|s| !s.is_empty()).collect();
     let width = lines[0].len() as i32;
     let height = lines.len() as i32;
 
@@ -201,6 +284,15 @@ enum Direction {
 }
 
 impl Direction {
+    fn get_delta(&self) -> (i32, i32) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::West => (-1, 0),
+            Direction::South => (0, 1),
+            Direction::East => (1, 0),
+        }
+    }
+
     fn opposite(&self) -> Self {
         match self {
             Direction::North => Direction::South,
@@ -1,20 +1,41 @@
 use itertools::Itertools;
+use log::trace;
 use num_enum::TryFromPrimitive;
+use aoc2024_common::budget::{self, Budget, Exhausted};
+use aoc2024_common::config;
 use aoc2024_common::file::read_input_lines;
+use aoc2024_common::logging;
+
+const DAY: u32 = 17;
+
+const DEFAULT_MAX_STEPS: u64 = 100000;
 
 fn main() {
+    logging::init();
+    let budget = Budget::for_day(DAY);
+    let max_steps = config::get(DAY, "max_steps", DEFAULT_MAX_STEPS);
     let input = parse_input();
-    println!("Part 1: {}", solve_p1(&input));
-    println!("Part 2: {}", solve_p2(&input));
+    match solve_p1(&input, max_steps) {
+        Ok(ans) => println!("Part 1: {}", ans),
+        Err((reason, progress)) => budget::give_up(&reason, &progress),
+    }
+    match solve_p2(&input, max_steps, &budget) {
+        Ok(ans) => println!("Part 2: {}", ans),
+        Err((reason, progress)) => budget::give_up(&reason, &progress),
+    }
 }
 
-fn solve_p1(input: &ComputerInput) -> String {
+// why we stopped early, along with a description of how far we got
+type Stopped = (Exhausted, String);
+
+fn solve_p1(input: &ComputerInput, max_steps: u64) -> Result<String, Stopped> {
     let mut computer = Computer::from_input(input);
-    do_simulation(&mut computer);
-    computer.out.iter().map(u8::to_string).join(",")
+    do_simulation(&mut computer, max_steps)
+        .map_err(|reason| (reason, format!("program printed {:?} before stopping", computer.out)))?;
+    Ok(computer.out.iter().map(u8::to_string).join(","))
 }
 
-fn solve_p2(input: &ComputerInput) -> u64 {
+fn solve_p2(input: &ComputerInput, max_steps: u64, budget: &Budget) -> Result<u64, Stopped> {
     let mut computer = Computer::from_input(input);
 
     let target = computer.text.clone();
@@ -22,19 +43,22 @@ fn solve_p2(input: &ComputerInput) -> u64 {
     let mut exp = computer.text.len() - 1;
     let mut i = 1u64 << (exp * 3);
     loop {
+        let progress = || format!("matched {}/{} outputs, A = {:o}", target.len() - 1 - exp, target.len(), i);
+        budget.check().map_err(|reason| (reason, progress()))?;
+
         computer.a = i;
         computer.b = 0;
         computer.c = 0;
         computer.ip = 0;
         computer.out.clear();
 
-        do_simulation(&mut computer);
+        do_simulation(&mut computer, max_steps).map_err(|reason| (reason, progress()))?;
 
-        //println!("{:o} -> {:?}", i, computer.out);
+        trace!("{:o} -> {:?}", i, computer.out);
 
         if computer.out.len() <= target.len() && computer.out.len() > exp && computer.out[exp] == target[exp] {
             if exp == 0 {
-                return i;
+                return Ok(i);
             } else {
                 exp -= 1;
                 continue;
@@ -55,7 +79,7 @@ fn solve_p2(input: &ComputerInput) -> u64 {
 }
 
 fn parse_input() -> ComputerInput {
-    let lines = read_input_lines(17);
+    let lines = read_input_lines(DAY);
     ComputerInput {
         a: lines[0].split_once(":").unwrap().1.trim().parse::<u64>().unwrap(),
         b: lines[1].split_once(":").unwrap().1.trim().parse::<u64>().unwrap(),
@@ -64,14 +88,15 @@ fn parse_input() -> ComputerInput {
     }
 }
 
-fn do_simulation(computer: &mut Computer) {
+fn do_simulation(computer: &mut Computer, max_steps: u64) -> Result<(), Exhausted> {
     let mut steps = 0;
     while let Some(_) = do_simulation_step(computer) {
         steps += 1;
-        if steps > 100000 {
-            panic!("Program does not appear to halt");
+        if steps > max_steps {
+            return Err(Exhausted::Steps(max_steps));
         }
     }
+    Ok(())
 }
 
 fn do_simulation_step(computer: &mut Computer) -> Option<()> {
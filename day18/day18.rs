@@ -2,43 +2,62 @@ use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use itertools::Itertools;
+use log::{log_enabled, trace, Level};
+use aoc2024_common::config;
 use aoc2024_common::file::read_input_lines;
+use aoc2024_common::logging;
 
-const WIDTH: u32 = 71;
-const HEIGHT: u32 = 71;
+const DAY: u32 = 18;
+
+const DEFAULT_WIDTH: u32 = 71;
+const DEFAULT_HEIGHT: u32 = 71;
+const DEFAULT_INITIAL_BYTES: usize = 1024;
 
 fn main() {
-    let barriers: Vec<(u32, u32)> = read_input_lines(18).into_iter()
+    logging::init();
+    let barriers: Vec<(u32, u32)> = read_input_lines(DAY).into_iter()
         .map(|line| {
             let (x_str, y_str) = line.split_once(",").unwrap();
             (x_str.parse::<u32>().unwrap(), y_str.parse::<u32>().unwrap())
         })
         .collect();
-    println!("Part 1: {}", solve_p1(&barriers));
-    let ans_2 = solve_p2(&barriers);
+    let params = Params {
+        width: config::get(DAY, "width", DEFAULT_WIDTH),
+        height: config::get(DAY, "height", DEFAULT_HEIGHT),
+        initial_bytes: config::get(DAY, "initial_bytes", DEFAULT_INITIAL_BYTES),
+    };
+    println!("Part 1: {}", solve_p1(&params, &barriers));
+    let ans_2 = solve_p2(&params, &barriers);
     println!("Part 2: {},{}", ans_2.0, ans_2.1);
 }
 
-fn solve_p1(barriers: &Vec<(u32, u32)>) -> u64 {
-    match find_path(&HashSet::from_iter(barriers.iter().take(1024).copied()), (0, 0)) {
+#[derive(Clone, Copy, Debug)]
+struct Params {
+    width: u32,
+    height: u32,
+    initial_bytes: usize,
+}
+
+fn solve_p1(params: &Params, barriers: &[(u32, u32)]) -> u64 {
+    match find_path(params, &HashSet::from_iter(barriers.iter().take(params.initial_bytes).copied()), (0, 0)) {
         Some(path) => path.len() as u64,
         None => panic!("No path found"),
     }
 }
 
-fn solve_p2(barriers: &Vec<(u32, u32)>) -> (u32, u32) {
-    let mut cur_barriers = HashSet::from_iter(barriers.iter().take(1024).copied());
-    let mut cur_path = find_path(&cur_barriers, (0, 0)).expect("Could not find initial path");
-    for i in 1024..barriers.len() {
-        cur_barriers.insert(barriers[i]);
-        if let Some(trunc_len) = cur_path.iter().position(|p| p == &barriers[i]) {
+fn solve_p2(params: &Params, barriers: &[(u32, u32)]) -> (u32, u32) {
+    let mut cur_barriers = HashSet::from_iter(barriers.iter().take(params.initial_bytes).copied());
+    let mut cur_path = find_path(params, &cur_barriers, (0, 0)).expect("Could not find initial path");
+    for &barrier in barriers.iter().skip(params.initial_bytes) {
+        cur_barriers.insert(barrier);
+        if let Some(trunc_len) = cur_path.iter().position(|p| p == &barrier) {
             cur_path.truncate(trunc_len);
 
-            match find_path(&cur_barriers, *cur_path.last().unwrap()) {
+            match find_path(params, &cur_barriers, *cur_path.last().unwrap()) {
                 Some(new_suffix) => {
                     cur_path.extend(new_suffix);
                 }
-                None => { return barriers[i]; }
+                None => { return barrier; }
             }
         } else {
             continue;
@@ -47,12 +66,12 @@ fn solve_p2(barriers: &Vec<(u32, u32)>) -> (u32, u32) {
     panic!("No solution found");
 }
 
-fn find_path(barriers: &HashSet<(u32, u32)>, start_pos: (u32, u32)) -> Option<Vec<(u32, u32)>> {
+fn find_path(params: &Params, barriers: &HashSet<(u32, u32)>, start_pos: (u32, u32)) -> Option<Vec<(u32, u32)>> {
     fn h(cur: (u32, u32), goal: (u32, u32)) -> u32 {
         u32::abs_diff(cur.0, goal.0) + u32::abs_diff(cur.1, goal.1)
     }
 
-    let goal = (WIDTH - 1, HEIGHT - 1);
+    let goal = (params.width - 1, params.height - 1);
 
     let mut came_from = HashMap::new();
     let mut g_score = HashMap::from([(start_pos, 0u32)]);
@@ -69,27 +88,31 @@ fn find_path(barriers: &HashSet<(u32, u32)>, start_pos: (u32, u32)) -> Option<Ve
                 cur_backtrack_node = prev_pos;
             }
 
-            /*for i in 0..HEIGHT {
-                for j in 0..WIDTH {
-                    if barriers.contains(&(j, i)) {
-                        print!("#");
-                    } else if total_path.contains(&(j, i)) {
-                        print!("O");
-                    } else {
-                        print!(".");
+            if log_enabled!(Level::Trace) {
+                let mut map = String::new();
+                for i in 0..params.height {
+                    for j in 0..params.width {
+                        if barriers.contains(&(j, i)) {
+                            map.push('#');
+                        } else if total_path.contains(&(j, i)) {
+                            map.push('O');
+                        } else {
+                            map.push('.');
+                        }
                     }
+                    map.push('\n');
                 }
-                println!();
-            }*/
+                trace!("Found path of length {}:\n{}", total_path.len(), map);
+            }
 
             total_path.reverse();
             return Some(total_path);
         }
         for move_delta in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
             if move_delta.0 == -1 && cur_pos.0 == 0 ||
-                move_delta.0 == 1 && cur_pos.0 == WIDTH - 1 ||
+                move_delta.0 == 1 && cur_pos.0 == params.width - 1 ||
                 move_delta.1 == -1 && cur_pos.1 == 0 ||
-                move_delta.1 == 1 && cur_pos.1 == WIDTH - 1 {
+                move_delta.1 == 1 && cur_pos.1 == params.height - 1 {
                 continue;
             }
 
@@ -1,10 +1,32 @@
 use std::cmp::min;
 use std::collections::{HashMap, HashSet};
-use aoc2024_common::file::read_input_lines;
+use aoc2024_common::diff::{self, DiffConfig};
+use aoc2024_common::file::read_input_string;
 
+const DAY: u32 = 19;
 
 fn main() {
-    let (patterns, towels) = parse_input();
+    if let Some(cfg) = DiffConfig::from_args() {
+        diff::run(
+            DAY,
+            "part 1 (exhaustive search vs memoized combinations)",
+            &cfg,
+            |input| {
+                let (patterns, towels) = parse_input(input);
+                solve_p1(&patterns, &towels)
+            },
+            |input| {
+                let (patterns, towels) = parse_input(input);
+                let mut cache = HashMap::new();
+                towels.iter()
+                    .filter(|towel| get_towel_combinations(&patterns, towel.as_str(), &mut cache) > 0)
+                    .count() as u64
+            },
+        );
+        return;
+    }
+
+    let (patterns, towels) = parse_input(&read_input_string(DAY));
     println!("Part 1: {}", solve_p1(&patterns, &towels));
     println!("Part 2: {}", solve_p2(&patterns, &towels));
 }
@@ -30,8 +52,8 @@ fn solve_p2(patterns: &HashSet<String>, towels: &Vec<String>) -> u64 {
     possible_count
 }
 
-fn parse_input() -> (HashSet<String>, Vec<String>) {
-    let lines = read_input_lines(19);
+fn parse_input(input: &str) -> (HashSet<String>, Vec<String>) {
+    let lines: Vec<_> = input.split("\n").filter(|s| !s.is_empty()).collect();
     let patterns = lines[0].split(", ").map(|s| s.to_string()).collect::<HashSet<_>>();
     let towels = lines.iter().skip(1).map(|s| s.to_string()).collect::<Vec<_>>();
     (patterns, towels)
@@ -1,17 +1,22 @@
 use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
+use aoc2024_common::config;
 use aoc2024_common::file::read_input_lines;
 
+const DAY: u32 = 20;
+
 const PART_1_DIST: i32 = 2;
 const PART_2_DIST: i32 = 20;
+const DEFAULT_MIN_TIMESAVE: i32 = 100;
 
 fn main() {
     let maze = parse_input();
-    println!("Part 1: {}", solve(&maze, PART_1_DIST));
-    println!("Part 2: {}", solve(&maze, PART_2_DIST));
+    let min_timesave = config::get(DAY, "min_timesave", DEFAULT_MIN_TIMESAVE);
+    println!("Part 1: {}", solve(&maze, PART_1_DIST, min_timesave));
+    println!("Part 2: {}", solve(&maze, PART_2_DIST, min_timesave));
 }
 
-fn solve(maze: &MazeDef, cheat_dist: i32) -> u64 {
+fn solve(maze: &MazeDef, cheat_dist: i32, min_timesave: i32) -> u64 {
     let base_path = find_base_path(maze).into_iter().enumerate().map(|(i, p)| (p, i)).collect::<HashMap<_, _>>();
 
     let mut cheats = Vec::new();
@@ -46,7 +51,7 @@ fn solve(maze: &MazeDef, cheat_dist: i32) -> u64 {
     let counts = cheats.iter().counts_by(|(_, _, timesave, _, _)| *timesave);
 
     counts.into_iter()
-        .filter(|(timesave, _)| *timesave >= 100)
+        .filter(|(timesave, _)| *timesave >= min_timesave)
         .map(|(_, count)| count as u64)
         .sum()
 }
@@ -72,7 +77,7 @@ fn find_base_path(maze: &MazeDef) -> Vec<(u32, u32)> {
 }
 
 fn parse_input() -> MazeDef {
-    let lines = read_input_lines(20);
+    let lines = read_input_lines(DAY);
     let height = lines.len() as u32;
     let width = lines[0].len() as u32;
     let populated_tiles = lines.into_iter()
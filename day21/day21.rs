@@ -1,7 +1,10 @@
 use std::cmp::max;
 use std::collections::HashMap;
+use aoc2024_common::config;
 use aoc2024_common::file::read_input_lines;
 
+const DAY: u32 = 21;
+
 const NUMPAD_COORDS: &[(i32, i32)] = &[
     (1, 3), // 0
     (0, 2), // ...
@@ -20,10 +23,10 @@ const NUMPAD_BLANK_POS: (i32, i32) = (0, 3);
 const DIRPAD_BLANK_POS: (i32, i32) = (0, 0);
 
 const PART_1_INDIRECTION: u32 = 2;
-const PART_2_INDIRECTION: u32 = 25;
+const DEFAULT_PART_2_INDIRECTION: u32 = 25;
 
 fn main() {
-    let codes = read_input_lines(21).into_iter()
+    let codes = read_input_lines(DAY).into_iter()
         .map(|line| {
             line.chars()
                 .map(|c| if c == 'A' { 10 } else { c as usize - '0' as usize })
@@ -31,7 +34,7 @@ fn main() {
         })
         .collect();
     println!("Part 1: {}", solve(&codes, PART_1_INDIRECTION));
-    println!("Part 2: {}", solve(&codes, PART_2_INDIRECTION));
+    println!("Part 2: {}", solve(&codes, config::get(DAY, "part_2_indirection", DEFAULT_PART_2_INDIRECTION)));
 }
 
 fn solve(codes: &Vec<Vec<usize>>, indirection: u32) -> u64 {
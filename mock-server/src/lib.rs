@@ -0,0 +1,259 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const YEAR: u32 = 2024;
+
+/// Behaviour of the stand-in server. Anything not configured (missing inputs or answers) is
+/// answered the way the real site answers a locked day.
+#[derive(Clone, Debug)]
+pub struct MockConfig {
+    pub session: String,
+    pub inputs: HashMap<u32, String>,
+    pub answers: HashMap<(u32, u8), String>,
+    pub unlocked_through: u32,
+    /// Cooldown after a wrong answer, during which further submissions are refused.
+    pub answer_cooldown: Duration,
+    /// Requests arriving closer together than this get a 429.
+    pub min_request_interval: Duration,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self {
+            session: "mock-session".to_string(),
+            inputs: HashMap::new(),
+            answers: HashMap::new(),
+            unlocked_through: 25,
+            answer_cooldown: Duration::from_secs(60),
+            min_request_interval: Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    last_request: Option<Instant>,
+    cooldown_until: Option<Instant>,
+    solved: HashSet<(u32, u8)>,
+    submissions: Vec<(u32, u8, String)>,
+}
+
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockServer {
+    /// Binds to `addr` (use port 0 for an ephemeral port) and serves requests on a background
+    /// thread for the rest of the process' lifetime.
+    pub fn start(addr: &str, config: MockConfig) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let config = Arc::new(config);
+        let state = Arc::new(Mutex::new(MockState::default()));
+
+        let thread_state = state.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let config = config.clone();
+                let state = thread_state.clone();
+                thread::spawn(move || {
+                    if let Err(e) = handle_connection(stream, &config, &state) {
+                        eprintln!("Connection error: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(Self { addr, state })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// All answer submissions received so far, as `(day, part, answer)`.
+    pub fn submissions(&self) -> Vec<(u32, u8, String)> {
+        self.state.lock().unwrap().submissions.clone()
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    headers: HashMap<String, String>,
+    body: String,
+}
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn new(status: u16, body: &str) -> Self {
+        Self { status, body: body.to_string() }
+    }
+}
+
+fn handle_connection(stream: TcpStream, config: &MockConfig, state: &Mutex<MockState>) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let Some(req) = read_request(&mut reader)? else { return Ok(()); };
+    let res = route(&req, config, state);
+
+    let reason = match res.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        429 => "Too Many Requests",
+        _ => "Unknown",
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        res.status,
+        reason,
+        res.body.len(),
+        res.body,
+    )?;
+    stream.flush()
+}
+
+fn read_request(reader: &mut impl BufRead) -> std::io::Result<Option<Request>> {
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line)? == 0 {
+        return Ok(None);
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((k, v)) = line.split_once(':') {
+            headers.insert(k.trim().to_ascii_lowercase(), v.trim().to_string());
+        }
+    }
+
+    let content_len = headers.get("content-length").and_then(|v| v.parse::<usize>().ok()).unwrap_or(0);
+    let mut body = vec![0u8; content_len];
+    reader.read_exact(&mut body)?;
+
+    Ok(Some(Request { method, path, headers, body: String::from_utf8_lossy(&body).into_owned() }))
+}
+
+fn route(req: &Request, config: &MockConfig, state: &Mutex<MockState>) -> Response {
+    let now = Instant::now();
+    {
+        let mut state = state.lock().unwrap();
+        let too_fast = state.last_request.is_some_and(|last| now - last < config.min_request_interval);
+        state.last_request = Some(now);
+        if too_fast {
+            return Response::new(429, "Too many requests");
+        }
+    }
+
+    let segments: Vec<&str> = req.path.trim_matches('/').split('/').collect();
+    let (day, action) = match segments[..] {
+        [year, "day", day, action] if year == YEAR.to_string() => match day.parse::<u32>() {
+            Ok(day) => (day, action),
+            Err(_) => return Response::new(404, "404 Not Found"),
+        },
+        _ => return Response::new(404, "404 Not Found"),
+    };
+
+    if !is_authorized(req, config) {
+        return Response::new(400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.");
+    }
+    if day == 0 || day > config.unlocked_through {
+        return Response::new(404, "Please don't repeatedly request this endpoint before it unlocks! \
+            The calendar countdown is synchronized with the server time; the link will be enabled on \
+            the calendar the instant this puzzle becomes available.");
+    }
+
+    match (req.method.as_str(), action) {
+        ("GET", "input") => match config.inputs.get(&day) {
+            Some(input) => Response::new(200, input),
+            None => Response::new(404, "404 Not Found"),
+        },
+        ("POST", "answer") => submit(day, &req.body, config, state, now),
+        _ => Response::new(405, "Method Not Allowed"),
+    }
+}
+
+fn is_authorized(req: &Request, config: &MockConfig) -> bool {
+    req.headers.get("cookie").is_some_and(|cookies| {
+        cookies.split(';').any(|c| c.trim() == format!("session={}", config.session))
+    })
+}
+
+fn submit(day: u32, body: &str, config: &MockConfig, state: &Mutex<MockState>, now: Instant) -> Response {
+    let form: HashMap<String, String> = body.split('&')
+        .filter_map(|kv| kv.split_once('='))
+        .map(|(k, v)| (k.to_string(), url_decode(v)))
+        .collect();
+    let (Some(part), Some(answer)) = (form.get("level").and_then(|l| l.parse::<u8>().ok()), form.get("answer")) else {
+        return Response::new(400, "Bad Request");
+    };
+
+    let mut state = state.lock().unwrap();
+    state.submissions.push((day, part, answer.clone()));
+
+    if let Some(until) = state.cooldown_until.filter(|&until| now < until) {
+        return Response::new(200, &format!(
+            "You gave an answer too recently; you have to wait after submitting an answer before \
+            trying again.  You have {}s left to wait.",
+            (until - now).as_secs(),
+        ));
+    }
+    let expected_level = if state.solved.contains(&(day, 1)) { 2 } else { 1 };
+    if part != expected_level || state.solved.contains(&(day, 2)) {
+        return Response::new(200, "You don't seem to be solving the right level.  Did you already complete it?");
+    }
+
+    if config.answers.get(&(day, part)) == Some(answer) {
+        state.solved.insert((day, part));
+        Response::new(200, "That's the right answer!  You are one gold star closer to finding the Chief Historian.")
+    } else {
+        state.cooldown_until = Some(now + config.answer_cooldown);
+        Response::new(200, "That's not the right answer.  Please wait one minute before trying again.")
+    }
+}
+
+fn url_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(b) => {
+                        out.push(b);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
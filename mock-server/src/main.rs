@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use aoc2024_mock_server::{MockConfig, MockServer};
+
+const USAGE: &str = "\
+Usage: mock-server [--port N] [--session TOKEN] [--inputs DIR] [--unlocked-through DAY]
+                   [--cooldown SECS] [--min-interval MS]
+
+Inputs are read from DIR/dayNN.txt and expected answers from DIR/dayNN.answers, which holds
+\"Part 1: <answer>\" / \"Part 2: <answer>\" lines in the same format the days print.";
+
+fn main() {
+    let mut config = MockConfig::default();
+    let mut port = 8080;
+    let mut inputs_dir = None;
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().unwrap_or_else(|| fail(&format!("Missing value for {}", arg))).clone();
+        match arg.as_str() {
+            "--port" => port = parse(arg, &value()),
+            "--session" => config.session = value(),
+            "--inputs" => inputs_dir = Some(PathBuf::from(value())),
+            "--unlocked-through" => config.unlocked_through = parse(arg, &value()),
+            "--cooldown" => config.answer_cooldown = Duration::from_secs(parse(arg, &value())),
+            "--min-interval" => config.min_request_interval = Duration::from_millis(parse(arg, &value())),
+            _ => fail(&format!("Unknown argument '{}'", arg)),
+        }
+    }
+
+    if let Some(dir) = inputs_dir {
+        load_inputs(&dir, &mut config.inputs, &mut config.answers);
+    }
+
+    let server = MockServer::start(&format!("127.0.0.1:{}", port), config)
+        .unwrap_or_else(|e| fail(&format!("Failed to bind port {}: {}", port, e)));
+    println!("Serving mock Advent of Code at {}", server.base_url());
+    println!("Point the days at it with AOC_BASE_URL={}", server.base_url());
+    loop {
+        thread::park();
+    }
+}
+
+fn load_inputs(dir: &Path, inputs: &mut HashMap<u32, String>, answers: &mut HashMap<(u32, u8), String>) {
+    for day in 1..=25 {
+        if let Ok(input) = fs::read_to_string(dir.join(format!("day{:02}.txt", day))) {
+            inputs.insert(day, input);
+        }
+        if let Ok(content) = fs::read_to_string(dir.join(format!("day{:02}.answers", day))) {
+            for line in content.lines() {
+                let Some((label, answer)) = line.split_once(": ") else { continue; };
+                match label {
+                    "Part 1" => { answers.insert((day, 1), answer.trim().to_string()); },
+                    "Part 2" => { answers.insert((day, 2), answer.trim().to_string()); },
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+fn parse<T: std::str::FromStr>(flag: &str, value: &str) -> T {
+    value.parse::<T>().unwrap_or_else(|_| fail(&format!("Invalid value for {}", flag)))
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{}\n\n{}", msg, USAGE);
+    std::process::exit(1);
+}